@@ -0,0 +1,109 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "prt",
+    version,
+    about = "TUI for managing pull requests on GitHub"
+)]
+pub struct Cli {
+    /// Repository to work on as owner/name, instead of the checkout's origin
+    #[arg(long, global = true, value_name = "OWNER/NAME", value_parser = parse_repo)]
+    pub repo: Option<(String, String)>,
+
+    /// Run as if prt was started in <PATH>
+    #[arg(short = 'C', global = true, value_name = "PATH")]
+    pub directory: Option<PathBuf>,
+
+    /// Print machine-readable JSON instead of human-readable text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Non-interactive commands. Without one, prt starts the TUI.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create a pull request
+    Create(CreateArgs),
+    /// List pull requests
+    List(ListArgs),
+    /// Show a pull request
+    View {
+        /// Pull request number
+        number: u64,
+    },
+    /// Merge a pull request
+    Merge(MergeArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CreateArgs {
+    /// Pull request title
+    #[arg(long)]
+    pub title: String,
+
+    /// Pull request description
+    #[arg(long, conflicts_with = "body_file")]
+    pub body: Option<String>,
+
+    /// Read the description from a file, or from stdin with "-"
+    #[arg(long, value_name = "PATH")]
+    pub body_file: Option<PathBuf>,
+
+    /// Branch to merge into [default: the repository's default branch]
+    #[arg(long)]
+    pub base: Option<String>,
+
+    /// Branch with the changes [default: the current branch]
+    #[arg(long)]
+    pub head: Option<String>,
+
+    /// Open the pull request as a draft
+    #[arg(long)]
+    pub draft: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Only list pull requests in this state
+    #[arg(long, value_enum, default_value_t = ListState::Open)]
+    pub state: ListState,
+
+    /// Maximum number of pull requests to list
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub limit: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListState {
+    Open,
+    Closed,
+    All,
+}
+
+#[derive(Debug, Args)]
+pub struct MergeArgs {
+    /// Pull request number
+    pub number: u64,
+
+    /// Squash the commits into one
+    #[arg(long, group = "method")]
+    pub squash: bool,
+
+    /// Rebase the commits onto the base branch
+    #[arg(long, group = "method")]
+    pub rebase: bool,
+}
+
+pub fn parse_repo(value: &str) -> Result<(String, String), String> {
+    match value.split_once('/') {
+        Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => {
+            Ok((owner.to_string(), name.trim_end_matches(".git").to_string()))
+        }
+        _ => Err(format!("expected OWNER/NAME, got '{}'", value)),
+    }
+}
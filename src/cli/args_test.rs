@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::args::{parse_repo, Cli, Command, ListState};
+    use clap::Parser;
+
+    #[test]
+    fn test_parse_repo() {
+        assert_eq!(
+            parse_repo("djego/prt"),
+            Ok(("djego".to_string(), "prt".to_string()))
+        );
+        assert_eq!(
+            parse_repo("djego/prt.git"),
+            Ok(("djego".to_string(), "prt".to_string()))
+        );
+        assert!(parse_repo("prt").is_err());
+        assert!(parse_repo("/prt").is_err());
+        assert!(parse_repo("a/b/c").is_err());
+    }
+
+    #[test]
+    fn test_cli_arguments() {
+        let cli = Cli::parse_from(["prt", "--repo", "djego/prt", "-C", "/tmp"]);
+        assert_eq!(cli.repo, Some(("djego".to_string(), "prt".to_string())));
+        assert_eq!(cli.directory.as_deref(), Some(std::path::Path::new("/tmp")));
+    }
+
+    #[test]
+    fn test_create_command() {
+        let cli = Cli::parse_from([
+            "prt",
+            "create",
+            "--title",
+            "Add feature",
+            "--body-file",
+            "-",
+            "--base",
+            "develop",
+            "--draft",
+        ]);
+        let Some(Command::Create(args)) = cli.command else {
+            panic!("expected create command");
+        };
+        assert_eq!(args.title, "Add feature");
+        assert_eq!(args.body_file.as_deref(), Some(std::path::Path::new("-")));
+        assert_eq!(args.base.as_deref(), Some("develop"));
+        assert!(args.draft);
+    }
+
+    #[test]
+    fn test_list_command_accepts_json_after_subcommand() {
+        let cli = Cli::parse_from(["prt", "list", "--json"]);
+        assert!(cli.json);
+        let Some(Command::List(args)) = cli.command else {
+            panic!("expected list command");
+        };
+        assert_eq!(args.state, ListState::Open);
+        assert_eq!(args.limit, 30);
+    }
+
+    #[test]
+    fn test_merge_methods_are_exclusive() {
+        let cli = Cli::parse_from(["prt", "merge", "42", "--squash"]);
+        let Some(Command::Merge(args)) = cli.command else {
+            panic!("expected merge command");
+        };
+        assert_eq!(args.number, 42);
+        assert!(args.squash);
+        assert!(Cli::try_parse_from(["prt", "merge", "42", "--squash", "--rebase"]).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use crate::cli::args::{Command, CreateArgs, ListArgs, ListState, MergeArgs};
+use crate::core::app::App;
+use crate::core::errors::PullRequestError;
+use crate::core::field::Field;
+use crate::core::git::get_current_branch;
+use crate::core::pull_request::PullRequestSummary;
+use octocrab::params::pulls::MergeMethod;
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use tokio::runtime::Runtime;
+
+#[derive(Serialize)]
+struct MergeSummary {
+    number: u64,
+    merged: bool,
+    sha: Option<String>,
+    message: Option<String>,
+}
+
+/// Runs a subcommand against the same `App` the TUI uses, printing the
+/// result to stdout as text or JSON.
+pub fn run(
+    command: Command,
+    json: bool,
+    app: App,
+    runtime: &Runtime,
+) -> Result<(), PullRequestError> {
+    if app.config_pat.is_empty() {
+        return Err(PullRequestError::InvalidInput(
+            "No GitHub PAT configured, run prt once to save one".to_string(),
+        ));
+    }
+    if !app.has_repository() {
+        return Err(PullRequestError::InvalidInput(
+            "No repository found, run inside a checkout or pass --repo OWNER/NAME".to_string(),
+        ));
+    }
+
+    match command {
+        Command::Create(args) => create(args, json, app, runtime),
+        Command::List(args) => list(args, json, app, runtime),
+        Command::View { number } => view(number, json, app, runtime),
+        Command::Merge(args) => merge(args, json, app, runtime),
+    }
+}
+
+fn create(
+    args: CreateArgs,
+    json: bool,
+    mut app: App,
+    runtime: &Runtime,
+) -> Result<(), PullRequestError> {
+    let description = match (args.body, args.body_file) {
+        (Some(body), _) => body,
+        (None, Some(path)) => read_body_file(&path)?,
+        (None, None) => String::new(),
+    };
+    app.form.set(Field::Title, &args.title);
+    app.form.set(Field::Description, &description);
+    app.form.draft = args.draft;
+    // A detached checkout has no branch to open the pull request from.
+    let current_branch = || get_current_branch().filter(|branch| branch != "HEAD");
+    let Some(head) = args.head.or_else(current_branch) else {
+        return Err(PullRequestError::InvalidInput(
+            "No branch is checked out here, pass the branch with the changes as --head".to_string(),
+        ));
+    };
+    app.form.set(Field::SourceBranch, &head);
+    match args.base {
+        Some(base) => app.form.set(Field::TargetBranch, &base),
+        None => {
+            let repo = runtime.block_on(app.fetch_github_repo_info())?;
+            if let Some(branch) = repo.default_branch {
+                app.form.set(Field::TargetBranch, &branch);
+            }
+        }
+    }
+
+    let pr = runtime.block_on(app.create_github_pull_request())?;
+    let summary = PullRequestSummary::from(&pr);
+    print_output(&summary, json, |pr| {
+        format!("Created pull request #{}: {}", pr.number, pr.url)
+    });
+    Ok(())
+}
+
+fn list(args: ListArgs, json: bool, app: App, runtime: &Runtime) -> Result<(), PullRequestError> {
+    let state = match args.state {
+        ListState::Open => "open",
+        ListState::Closed => "closed",
+        ListState::All => "all",
+    };
+    let prs = runtime.block_on(app.list_github_pull_requests(state, args.limit))?;
+    let summaries: Vec<PullRequestSummary> = prs.iter().map(PullRequestSummary::from).collect();
+    print_output(&summaries, json, |prs| {
+        if prs.is_empty() {
+            return "No pull requests found".to_string();
+        }
+        prs.iter()
+            .map(|pr| {
+                format!(
+                    "#{}\t{}{}\t{} -> {}\t{} (@{})",
+                    pr.number,
+                    pr.state,
+                    if pr.draft { " (draft)" } else { "" },
+                    pr.source_branch,
+                    pr.target_branch,
+                    pr.title,
+                    pr.author
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+    Ok(())
+}
+
+fn view(number: u64, json: bool, app: App, runtime: &Runtime) -> Result<(), PullRequestError> {
+    let pr = runtime.block_on(app.get_github_pull_request(number))?;
+    let status = runtime.block_on(app.fetch_pull_request_status(&pr))?;
+    let mut summary = PullRequestSummary::from(&pr);
+    summary.checks = status.runs;
+    print_output(&summary, json, |pr| {
+        let mergeable = match pr.mergeable {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "unknown",
+        };
+        let mut text = format!(
+            "#{} {}\nState: {}{}\nAuthor: @{}\nBranches: {} -> {}\nMergeable: {}\nURL: {}",
+            pr.number,
+            pr.title,
+            pr.state,
+            if pr.draft { " (draft)" } else { "" },
+            pr.author,
+            pr.source_branch,
+            pr.target_branch,
+            mergeable,
+            pr.url
+        );
+        if !pr.checks.is_empty() {
+            text.push_str("\n\nChecks:");
+            for check in &pr.checks {
+                text.push_str(&format!(
+                    "\n {} {}\t{}\t{}",
+                    check.outcome.symbol(),
+                    check.name,
+                    check.outcome.label(),
+                    check.duration_label()
+                ));
+            }
+        }
+        if let Some(body) = pr.body.as_deref().filter(|body| !body.is_empty()) {
+            text.push_str("\n\n");
+            text.push_str(body);
+        }
+        text
+    });
+    Ok(())
+}
+
+fn merge(args: MergeArgs, json: bool, app: App, runtime: &Runtime) -> Result<(), PullRequestError> {
+    let method = if args.squash {
+        MergeMethod::Squash
+    } else if args.rebase {
+        MergeMethod::Rebase
+    } else {
+        MergeMethod::Merge
+    };
+    let result = runtime.block_on(app.merge_github_pull_request(args.number, method))?;
+    let summary = MergeSummary {
+        number: args.number,
+        merged: result.merged,
+        sha: result.sha,
+        message: result.message,
+    };
+    print_output(&summary, json, |merge| {
+        if merge.merged {
+            format!(
+                "Merged pull request #{} ({})",
+                merge.number,
+                merge.sha.as_deref().unwrap_or("-")
+            )
+        } else {
+            format!(
+                "Pull request #{} was not merged: {}",
+                merge.number,
+                merge.message.as_deref().unwrap_or("-")
+            )
+        }
+    });
+    Ok(())
+}
+
+fn read_body_file(path: &Path) -> Result<String, PullRequestError> {
+    let result = if path == Path::new("-") {
+        let mut body = String::new();
+        io::stdin().read_to_string(&mut body).map(|_| body)
+    } else {
+        fs::read_to_string(path)
+    };
+    result.map_err(|e| {
+        PullRequestError::InvalidInput(format!("Cannot read {}: {}", path.display(), e))
+    })
+}
+
+fn print_output<T: Serialize>(value: &T, json: bool, human: impl FnOnce(&T) -> String) {
+    if json {
+        let output = serde_json::to_string_pretty(value).expect("Failed to serialize output");
+        println!("{}", output);
+    } else {
+        println!("{}", human(value));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod args;
+pub mod args_test;
+pub mod commands;
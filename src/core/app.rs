@@ -1,27 +1,119 @@
-use crate::core::config::load_config;
+use crate::core::cache::ResponseCache;
+use crate::core::checks::{log_tail, ActionsJob, Check, LOG_TAIL_LINES};
+use crate::core::client::{
+    encode_path, encode_query, web_url, GithubClient, RateLimit, SharedRateLimit, GITHUB_API_URL,
+    MAX_PAGES,
+};
+use crate::core::compare::Comparison;
+use crate::core::config::{load_config, Config};
+use crate::core::dashboard::{
+    merge_results, sort_dashboard, Dashboard, DashboardEntry, Involvement, SearchResults, UserTeam,
+    DASHBOARD_LOOKUPS, DASHBOARD_MAX_TEAMS, DASHBOARD_REFRESH, DASHBOARD_SEARCH_LIMIT,
+};
 use crate::core::errors::PullRequestError;
+use crate::core::field::Field;
+use crate::core::form::PullRequestForm;
 use crate::core::git::{get_current_branch, get_repo_info};
 use crate::core::github::GithubRepository;
 use crate::core::input_mode::InputMode;
-use crate::core::pull_request::PullRequest;
-use octocrab::models::pulls::PullRequest as OctocrabPullRequest;
-use octocrab::{models::Repository, Octocrab};
+use crate::core::keymap::{Action, Context, Keymap};
+use crate::core::message_log::{Message, MessageLog, Severity};
+use crate::core::notifications::{sort_inbox, InboxItem};
+use crate::core::palette::CommandPalette;
+use crate::core::pull_request::{ChecksState, PullRequestStatus, ReviewDecision};
+use crate::core::screen::Screen;
+use crate::core::text_input::TextInput;
+use crate::core::theme::{no_color, Theme};
+use crate::core::timeline::{
+    build_timeline, next_threads_cursor, review_threads, ReviewThread, TimelineEntry, TimelineKind,
+    RESOLVE_THREAD_MUTATION, REVIEW_THREADS_QUERY, UNRESOLVE_THREAD_MUTATION,
+};
+use chrono::Utc;
+use crossterm::event::KeyEvent;
+use octocrab::models::activity::Notification;
+use octocrab::models::checks::{CheckRun, ListCheckRuns};
+use octocrab::models::commits::CommitComparison;
+use octocrab::models::pulls::{Merge, PullRequest as OctocrabPullRequest, Review};
+use octocrab::models::{Author, CombinedStatus, Repository};
+use octocrab::params::pulls::MergeMethod;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use tui_textarea::TextArea;
 
+pub struct SyncResult {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub repository: Repository,
+    pub existing: Option<(OctocrabPullRequest, PullRequestStatus)>,
+    /// What `existing` was looked up for: the opened pull request, or else
+    /// the open one for this source branch.
+    pub opened_pull_request: Option<u64>,
+    pub source_branch: String,
+    /// Who the token belongs to, or why that couldn't be found out, when it
+    /// wasn't known yet.
+    pub token_user: Option<Result<String, String>>,
+}
+
+#[derive(Clone)]
 pub struct App {
-    pub error_message: Option<String>,
-    pub success_message: Option<String>,
-    pub pull_request: PullRequest,
+    pub messages: MessageLog,
+    pub form: PullRequestForm,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    /// The command typed after `:` or `Alt+x`, while it is open.
+    pub command_line: Option<TextInput>,
+    pub palette: Option<CommandPalette>,
     pub input_mode: InputMode,
+    pub screen: Screen,
     pub current_field: usize,
     pub show_confirm_popup: bool,
+    /// Lines of the comparison scrolled past in the confirmation popup.
+    pub confirm_scroll: u16,
     pub show_pat_popup: bool,
     pub show_exit_popup: bool,
+    pub show_help: bool,
+    /// Lines scrolled past in the help overlay.
+    pub help_scroll: u16,
+    pub show_repo_popup: bool,
+    pub repositories: Vec<String>,
+    pub selected_repository: usize,
+    pub notifications: Vec<InboxItem>,
+    pub selected_notification: usize,
+    pub dashboard: Vec<DashboardEntry>,
+    pub selected_dashboard_entry: usize,
+    pub dashboard_loading: bool,
+    pub dashboard_refreshed_at: Option<Instant>,
+    pub timeline: Vec<TimelineEntry>,
+    pub timeline_pull_request: Option<u64>,
+    pub selected_timeline_entry: usize,
+    pub composing_reply: bool,
+    pub reply_text_area: TextArea<'static>,
+    pub current_pull_request: Option<OctocrabPullRequest>,
+    /// The pull request opened from the inbox or the dashboard. Syncs follow
+    /// it by number instead of looking one up for the source branch.
+    pub opened_pull_request: Option<u64>,
+    pub pull_request_status: Option<PullRequestStatus>,
+    pub selected_check: usize,
+    pub editing_pull_request: Option<u64>,
+    /// The form as it was before an existing pull request was loaded into
+    /// it, brought back by `reset` so the checked-out branch isn't lost.
+    pub form_before_edit: Option<PullRequestForm>,
+    pub form_open: bool,
+    pub show_preview: bool,
+    pub comparison: Option<Comparison>,
     pub github_repository: GithubRepository,
     pub repo_owner: String,
     pub repo_name: String,
     pub config_pat: String,
-    pub description_text_area: TextArea<'static>,
+    /// The login the token belongs to, once the help overlay looked it up.
+    pub token_user: Option<Result<String, String>>,
+    pub api_url: String,
+    pub rate_limit: SharedRateLimit,
+    pub cache_only: bool,
     pub pat_input: TextArea<'static>,
 }
 
@@ -32,126 +124,603 @@ impl App {
             None => ("-".to_string(), "-".to_string()),
         };
         let current_branch = get_current_branch().unwrap_or_else(|| "-".to_string());
+        let (config, config_error) = match load_config() {
+            Ok(config) => (config, None),
+            Err(e) => (None, Some(e)),
+        };
+        let mut app = App::with_config(repo_owner, repo_name, &current_branch, config, no_color());
+        if let Some(e) = config_error {
+            app.messages.push(Message::new(
+                Severity::Warning,
+                format!("Config: {}, using the defaults", e),
+            ));
+        }
+        app
+    }
 
-        let config_pat = load_config()
-            .map(|config| config.github.pat)
+    /// An app for `repo_owner/repo_name` with `current_branch` checked out,
+    /// set up from `config` without looking at the git checkout, the config
+    /// file or the environment.
+    pub fn with_config(
+        repo_owner: String,
+        repo_name: String,
+        current_branch: &str,
+        config: Option<Config>,
+        no_color: bool,
+    ) -> App {
+        let config_pat = config
+            .as_ref()
+            .map(|config| config.github.pat.clone())
             .unwrap_or_else(|| String::from(""));
+        let (keymap_config, theme_config) = config
+            .map(|config| (config.keymap, config.theme))
+            .unwrap_or_default();
+        let (keymap, keymap_warnings) = Keymap::from_config(&keymap_config);
+        let (theme, theme_warnings) = Theme::from_config(&theme_config, no_color);
+        let mut messages = MessageLog::default();
+        if !keymap_warnings.is_empty() {
+            messages.push(Message::new(
+                Severity::Warning,
+                format!("Keymap: {}", keymap_warnings.join("; ")),
+            ));
+        }
+        if !theme_warnings.is_empty() {
+            messages.push(Message::new(
+                Severity::Warning,
+                format!("Theme: {}", theme_warnings.join("; ")),
+            ));
+        }
 
-        let text_area = TextArea::default();
         let pat_input = TextArea::default();
 
         App {
-            pull_request: PullRequest::new(current_branch.clone(), "main".to_string()),
+            form: PullRequestForm::new(current_branch, "main"),
+            keymap,
+            theme,
+            command_line: None,
+            palette: None,
             input_mode: InputMode::Normal,
+            screen: Screen::Main,
             current_field: 0,
             show_confirm_popup: false,
+            confirm_scroll: 0,
             show_pat_popup: false,
             show_exit_popup: false,
-            error_message: None,
-            success_message: None,
+            show_help: false,
+            help_scroll: 0,
+            show_repo_popup: false,
+            repositories: Vec::new(),
+            selected_repository: 0,
+            notifications: Vec::new(),
+            selected_notification: 0,
+            dashboard: Vec::new(),
+            selected_dashboard_entry: 0,
+            dashboard_loading: false,
+            dashboard_refreshed_at: None,
+            timeline: Vec::new(),
+            timeline_pull_request: None,
+            selected_timeline_entry: 0,
+            composing_reply: false,
+            reply_text_area: TextArea::default(),
+            current_pull_request: None,
+            opened_pull_request: None,
+            pull_request_status: None,
+            selected_check: 0,
+            editing_pull_request: None,
+            form_before_edit: None,
+            form_open: false,
+            show_preview: false,
+            comparison: None,
+            messages,
             config_pat,
+            token_user: None,
+            api_url: GITHUB_API_URL.to_string(),
+            rate_limit: SharedRateLimit::default(),
+            cache_only: false,
             github_repository: GithubRepository::new(),
             repo_owner,
             repo_name,
-            description_text_area: text_area,
             pat_input,
         }
     }
 
+    /// Client for every GitHub call made by the app. All clients share the
+    /// same rate limit so the status bar shows the latest quota.
+    pub fn client(&self) -> Result<GithubClient, PullRequestError> {
+        let client = GithubClient::new(&self.config_pat, &self.api_url, self.rate_limit.clone())?
+            .with_cache(ResponseCache::in_config_dir());
+        Ok(if self.cache_only {
+            client.cache_only()
+        } else {
+            client
+        })
+    }
+
+    /// Copy of the app whose API calls are answered from the response cache
+    /// only, used to show the last known state before the network answers.
+    pub fn cached(&self) -> App {
+        App {
+            cache_only: true,
+            ..self.sync_snapshot()
+        }
+    }
+
+    /// Fetches the repository metadata and the opened pull request, or else
+    /// the open one for the source branch.
+    pub async fn fetch_sync(&self) -> Result<SyncResult, PullRequestError> {
+        let repository = self.fetch_github_repo_info().await?;
+        let existing = self.fetch_existing_pull_request().await?;
+        Ok(SyncResult {
+            repo_owner: self.repo_owner.clone(),
+            repo_name: self.repo_name.clone(),
+            repository,
+            existing,
+            opened_pull_request: self.opened_pull_request,
+            source_branch: self.form.value(Field::SourceBranch),
+            token_user: self.lookup_token_user().await,
+        })
+    }
+
+    /// A copy of the app holding only what a sync reads: the repository,
+    /// the source branch, the opened pull request and the API settings.
+    /// Background syncs run on it rather than on a copy of the whole UI.
+    pub fn sync_snapshot(&self) -> App {
+        let mut app = App::with_config(
+            self.repo_owner.clone(),
+            self.repo_name.clone(),
+            &self.form.value(Field::SourceBranch),
+            None,
+            true,
+        );
+        app.opened_pull_request = self.opened_pull_request;
+        app.config_pat = self.config_pat.clone();
+        app.token_user = self.token_user.clone();
+        app.api_url = self.api_url.clone();
+        app.rate_limit = self.rate_limit.clone();
+        app.cache_only = self.cache_only;
+        app
+    }
+
+    /// Applies the remote data of a sync, unless the user switched
+    /// repositories since it started. The target branch follows the default
+    /// branch until the user types one, and the pull request is only
+    /// replaced when the sync looked it up for what is still on screen.
+    pub fn apply_sync(&mut self, sync: SyncResult) -> bool {
+        if sync.token_user.is_some() {
+            self.token_user = sync.token_user;
+        }
+        if sync.repo_owner != self.repo_owner || sync.repo_name != self.repo_name {
+            return false;
+        }
+        if let Some(link) = sync.repository.html_url {
+            self.github_repository.set_url(link.to_string());
+        }
+        if let Some(branch) = sync.repository.default_branch {
+            self.github_repository.set_default_branch(branch.clone());
+            if self.editing_pull_request.is_none() && !self.form.is_edited(Field::TargetBranch) {
+                self.form.set(Field::TargetBranch, &branch);
+            }
+        }
+
+        let looked_up_current = sync.opened_pull_request == self.opened_pull_request
+            && (sync.opened_pull_request.is_some()
+                || sync.source_branch == self.form.value(Field::SourceBranch));
+        let (pr, status) = sync.existing.unzip();
+        let keeps_edited = self
+            .editing_pull_request
+            .is_some_and(|number| pr.as_ref().map(|pr| pr.number) != Some(number));
+        if looked_up_current && !keeps_edited {
+            self.current_pull_request = pr;
+            self.pull_request_status = status;
+        }
+        true
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    fn repo_route(&self, path: &str) -> String {
+        format!("/repos/{}/{}{}", self.repo_owner, self.repo_name, path)
+    }
+
+    /// Route under the repository `pr` targets, which may not be the
+    /// current one for pull requests found through search.
+    fn pull_request_route(&self, pr: &OctocrabPullRequest, path: &str) -> String {
+        match pr
+            .base
+            .repo
+            .as_ref()
+            .and_then(|repo| repo.full_name.as_ref())
+        {
+            Some(full_name) => format!("/repos/{}{}", full_name, path),
+            None => self.repo_route(path),
+        }
+    }
+
     pub async fn create_github_pull_request(
         &self,
     ) -> Result<OctocrabPullRequest, PullRequestError> {
-        let pat = self.config_pat.clone();
-        let octocrab = Octocrab::builder().personal_token(pat).build()?;
-        if self.pull_request.source_branch.is_empty() {
+        let pull_request = self.form.validate()?;
+        let client = self.client()?;
+
+        client
+            .post(
+                &self.repo_route("/pulls"),
+                &json!({
+                    "title": pull_request.title,
+                    "head": pull_request.source_branch,
+                    "base": pull_request.target_branch,
+                    "body": pull_request.description,
+                    "draft": pull_request.draft,
+                }),
+            )
+            .await
+    }
+
+    pub async fn fetch_comparison(&self) -> Result<Comparison, PullRequestError> {
+        let client = self.client()?;
+        let source = &self.form.value(Field::SourceBranch);
+        let target = &self.form.value(Field::TargetBranch);
+
+        let comparison: CommitComparison = match client
+            .get(&self.repo_route(&format!(
+                "/compare/{}...{}?per_page=100",
+                encode_path(target),
+                encode_path(source)
+            )))
+            .await
+        {
+            Ok(comparison) => comparison,
+            Err(PullRequestError::NotFound(_)) => {
+                return Err(PullRequestError::InvalidInput(format!(
+                    "Cannot compare {} with {}, check that both branches exist on GitHub",
+                    target, source
+                )))
+            }
+            Err(e) => return Err(e),
+        };
+
+        // An edit updates that pull request, so it isn't a duplicate.
+        let existing = match self.editing_pull_request {
+            Some(_) => None,
+            None => self.find_open_pull_request(&client).await?,
+        };
+
+        Ok(Comparison::new(
+            source.clone(),
+            target.clone(),
+            &comparison,
+            existing,
+        ))
+    }
+
+    /// Number of the open pull request whose head is the source branch.
+    async fn find_open_pull_request(
+        &self,
+        client: &GithubClient,
+    ) -> Result<Option<u64>, PullRequestError> {
+        let head = format!(
+            "{}:{}",
+            self.repo_owner,
+            self.form.value(Field::SourceBranch)
+        );
+        let open: Vec<OctocrabPullRequest> = client
+            .get(&self.repo_route(&format!(
+                "/pulls?state=open&head={}&per_page=1",
+                encode_query(&head)
+            )))
+            .await?;
+        Ok(open.first().map(|pr| pr.number))
+    }
+
+    /// Looks up the opened pull request, or else the open one whose head is
+    /// the current source branch, along with its checks and review state.
+    pub async fn fetch_existing_pull_request(
+        &self,
+    ) -> Result<Option<(OctocrabPullRequest, PullRequestStatus)>, PullRequestError> {
+        let client = self.client()?;
+
+        let number = match self.opened_pull_request {
+            Some(number) => Some(number),
+            None => self.find_open_pull_request(&client).await?,
+        };
+        let Some(number) = number else {
+            return Ok(None);
+        };
+
+        let pr = self.get_github_pull_request(number).await?;
+        let status = self.fetch_pull_request_status(&pr).await?;
+        Ok(Some((pr, status)))
+    }
+
+    /// Fetches the checks of the head commit of `pr` and its review state.
+    pub async fn fetch_pull_request_status(
+        &self,
+        pr: &OctocrabPullRequest,
+    ) -> Result<PullRequestStatus, PullRequestError> {
+        pull_request_status(&self.client()?, &self.pull_request_route(pr, ""), pr).await
+    }
+
+    /// Last lines of the log of a GitHub Actions job.
+    pub async fn fetch_check_log(&self, job: ActionsJob) -> Result<Vec<String>, PullRequestError> {
+        let client = self.client()?;
+
+        let log = client
+            .get_text(&self.repo_route(&format!("/actions/jobs/{}/logs", job.job_id)))
+            .await?;
+        Ok(log_tail(&log, LOG_TAIL_LINES))
+    }
+
+    /// Re-runs the failed jobs of every workflow run with a failed check,
+    /// returning how many workflow runs were restarted.
+    pub async fn rerun_failed_checks(&self, checks: &[Check]) -> Result<usize, PullRequestError> {
+        let client = self.client()?;
+
+        let mut run_ids: Vec<u64> = checks
+            .iter()
+            .filter(|check| check.is_failure())
+            .filter_map(|check| check.job.map(|job| job.run_id))
+            .collect();
+        run_ids.sort_unstable();
+        run_ids.dedup();
+        if run_ids.is_empty() {
             return Err(PullRequestError::InvalidInput(
-                "Source branch is empty".to_string(),
+                "There are no failed GitHub Actions jobs to re-run".to_string(),
             ));
         }
-        if self.pull_request.target_branch.is_empty() {
-            return Err(PullRequestError::InvalidInput(
-                "Target branch is empty".to_string(),
-            ));
+
+        for run_id in &run_ids {
+            let _: Value = client
+                .post(
+                    &self.repo_route(&format!("/actions/runs/{}/rerun-failed-jobs", run_id)),
+                    &json!({}),
+                )
+                .await?;
         }
+        Ok(run_ids.len())
+    }
+
+    pub async fn update_github_pull_request(
+        &self,
+        number: u64,
+    ) -> Result<OctocrabPullRequest, PullRequestError> {
+        let pull_request = self.form.validate()?;
+        let client = self.client()?;
 
-        let pr_result = octocrab
-            .pulls(&self.repo_owner, &self.repo_name)
-            .create(
-                &self.pull_request.title,
-                &self.pull_request.source_branch,
-                &self.pull_request.target_branch,
+        match client
+            .patch(
+                &self.repo_route(&format!("/pulls/{}", number)),
+                &json!({
+                    "title": pull_request.title,
+                    "body": pull_request.description,
+                    "base": pull_request.target_branch,
+                }),
             )
-            .body(&self.pull_request.description)
-            .send()
-            .await;
-
-        match pr_result {
-            Ok(pr) => Ok(pr),
-            Err(e) => {
-                if let octocrab::Error::GitHub { source, .. } = &e {
-                    match source.status_code.as_u16() {
-                        422 => {
-                            return Err(PullRequestError::PullRequestValidationFailed(
-                                e.to_string(),
-                            ));
-                        }
-                        _ => Err(PullRequestError::ApiError(e)),
-                    }
-                } else {
-                    Err(PullRequestError::ApiError(e))
-                }
+            .await
+        {
+            Err(PullRequestError::NotFound(_)) => {
+                Err(PullRequestError::PullRequestNotFound(number))
+            }
+            result => result,
+        }
+    }
+
+    /// Merges the target branch into the head branch of pull request `number`.
+    pub async fn update_github_pull_request_branch(
+        &self,
+        number: u64,
+    ) -> Result<bool, PullRequestError> {
+        let client = self.client()?;
+
+        let _: Value = client
+            .put(
+                &self.repo_route(&format!("/pulls/{}/update-branch", number)),
+                &json!({}),
+            )
+            .await?;
+        Ok(true)
+    }
+
+    /// Lists pull requests in `state` ("open", "closed" or "all").
+    pub async fn list_github_pull_requests(
+        &self,
+        state: &str,
+        limit: u8,
+    ) -> Result<Vec<OctocrabPullRequest>, PullRequestError> {
+        let client = self.client()?;
+
+        client
+            .get(&self.repo_route(&format!("/pulls?state={}&per_page={}", state, limit)))
+            .await
+    }
+
+    pub async fn get_github_pull_request(
+        &self,
+        number: u64,
+    ) -> Result<OctocrabPullRequest, PullRequestError> {
+        let client = self.client()?;
+
+        match client
+            .get(&self.repo_route(&format!("/pulls/{}", number)))
+            .await
+        {
+            Err(PullRequestError::NotFound(_)) => {
+                Err(PullRequestError::PullRequestNotFound(number))
+            }
+            result => result,
+        }
+    }
+
+    pub async fn merge_github_pull_request(
+        &self,
+        number: u64,
+        method: MergeMethod,
+    ) -> Result<Merge, PullRequestError> {
+        let client = self.client()?;
+
+        match client
+            .put(
+                &self.repo_route(&format!("/pulls/{}/merge", number)),
+                &json!({ "merge_method": method }),
+            )
+            .await
+        {
+            Err(PullRequestError::NotFound(_)) => {
+                Err(PullRequestError::PullRequestNotFound(number))
+            }
+            // The merge endpoint answers 405 and 409 when the pull request
+            // can't be merged; elsewhere they mean other conflicts.
+            Err(PullRequestError::Conflict(message)) => {
+                Err(PullRequestError::PullRequestNotMergeable(message))
             }
+            result => result,
         }
     }
 
-    pub fn get_current_field_mut(&mut self) -> &mut String {
-        match self.current_field {
-            0 => &mut self.pull_request.title,
-            1 => &mut self.pull_request.description,
-            2 => &mut self.pull_request.source_branch,
-            3 => &mut self.pull_request.target_branch,
-            _ => unreachable!(),
+    /// The focused form field.
+    pub fn field(&self) -> Field {
+        Field::from_index(self.current_field)
+    }
+
+    /// Passes a key press to the focused field's editor.
+    pub fn input_key(&mut self, key: KeyEvent) -> bool {
+        let field = self.field();
+        self.form.input_key(field, key)
+    }
+
+    /// Inserts pasted text into the focused field.
+    pub fn paste(&mut self, text: &str) -> bool {
+        let field = self.field();
+        self.form.paste(field, text)
+    }
+
+    /// Name of the temporary file the current field is edited in, which also
+    /// lets the editor pick the syntax. `None` for fields without an editor.
+    pub fn editor_file_name(&self) -> Option<&'static str> {
+        match self.field() {
+            Field::Title => Some("PR_TITLE"),
+            Field::Description => Some("PR_DESCRIPTION.md"),
+            Field::SourceBranch | Field::TargetBranch => None,
+        }
+    }
+
+    /// Replaces the current field with text written in the external editor.
+    /// Titles are single line, so only the first non-empty line is kept.
+    pub fn apply_editor_text(&mut self, text: String) {
+        match self.field() {
+            Field::Title => {
+                let title = text
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .unwrap_or_default();
+                self.form.set(Field::Title, title);
+            }
+            Field::Description => self.form.set(Field::Description, &text),
+            Field::SourceBranch | Field::TargetBranch => {}
         }
     }
 
     pub fn enter_edit_mode(&mut self, index: usize) {
         self.input_mode = InputMode::Editing;
         self.current_field = index;
+        self.form_open = true;
+    }
+
+    /// The existing pull request replaces the create form until the user
+    /// starts creating or editing one.
+    pub fn is_showing_pull_request(&self) -> bool {
+        self.current_pull_request.is_some() && !self.form_open
+    }
+
+    /// The context keys are looked up in on the current screen, under any
+    /// overlay.
+    pub fn context(&self) -> Context {
+        match self.screen {
+            Screen::Notifications => Context::Inbox,
+            Screen::Dashboard => Context::Dashboard,
+            Screen::Timeline if self.composing_reply => Context::Reply,
+            Screen::Timeline => Context::Timeline,
+            Screen::Messages => Context::Messages,
+            Screen::Main => match self.input_mode {
+                InputMode::Normal => Context::Normal,
+                InputMode::Editing => Context::Editing,
+                InputMode::Creating => Context::Confirm,
+            },
+        }
+    }
+
+    /// The context keys are looked up in right now: the help overlay when
+    /// it is open, the current screen otherwise.
+    pub fn key_context(&self) -> Context {
+        if self.show_help {
+            Context::Help
+        } else {
+            self.context()
+        }
+    }
+
+    pub fn edit_existing_pull_request(&mut self) {
+        let Some(ref pr) = self.current_pull_request else {
+            return;
+        };
+        let description = pr.body.clone().unwrap_or_default();
+        let mut form = PullRequestForm::new(&pr.head.ref_field, &pr.base.ref_field);
+        form.set(Field::Title, pr.title.as_deref().unwrap_or_default());
+        form.set(Field::Description, &description);
+        self.editing_pull_request = Some(pr.number);
+        let previous = std::mem::replace(&mut self.form, form);
+        self.form_before_edit.get_or_insert(previous);
+        self.enter_edit_mode(0);
     }
 
     pub fn confirm_pull_request(&mut self) {
         self.input_mode = InputMode::Creating;
         self.show_confirm_popup = true;
+        self.confirm_scroll = 0;
     }
 
     pub fn reset(&mut self) {
-        self.pull_request = PullRequest::new(
-            self.pull_request.source_branch.clone(),
-            self.pull_request.target_branch.clone(),
-        );
+        if let Some(form) = self.form_before_edit.take() {
+            self.form = form;
+        }
+        self.form.clear();
         self.input_mode = InputMode::Normal;
         self.current_field = 0;
         self.show_confirm_popup = false;
-        self.description_text_area = TextArea::default();
+        self.editing_pull_request = None;
+        self.form_open = false;
+        self.comparison = None;
         self.clear_message();
     }
 
+    /// The message of `error` with its hint, naming the keys of the keymap
+    /// in use.
+    pub fn error_message(&self, error: &PullRequestError) -> String {
+        let sync_key = self
+            .keymap
+            .keys(Action::Sync)
+            .first()
+            .map(|key| format!("[{}]", key));
+        error.message_with_hint(sync_key.as_deref(), &self.web_url())
+    }
+
     pub fn set_error(&mut self, message: String) {
-        self.error_message = Some(message);
+        self.messages.push(Message::new(Severity::Error, message));
     }
 
     pub fn set_success(&mut self, success: String) {
-        self.success_message = Some(success);
+        self.messages.push(Message::new(Severity::Success, success));
     }
 
+    /// Takes down the toast of the last message, which stays in the log.
     pub fn clear_message(&mut self) {
-        self.success_message = None;
-        self.error_message = None;
+        self.messages.dismiss();
     }
 
     pub async fn fetch_github_repo_info(&self) -> Result<Repository, PullRequestError> {
-        let pat = self.config_pat.clone();
-        let octocrab = Octocrab::builder().personal_token(pat).build()?;
+        let client = self.client()?;
         if self.repo_name.is_empty() {
             return Err(PullRequestError::InvalidInput(
                 "Repository name is empty".to_string(),
@@ -163,27 +732,572 @@ impl App {
             ));
         }
 
-        let repo_result = octocrab
-            .repos(&self.repo_owner, &self.repo_name)
-            .get()
-            .await;
-        match repo_result {
-            Ok(repo) => Ok(repo),
-            Err(e) => {
-                if let octocrab::Error::GitHub { source, .. } = &e {
-                    match source.status_code.as_u16() {
-                        404 => {
-                            return Err(PullRequestError::RepoNotFound(e.to_string()));
-                        }
-                        _ => Err(PullRequestError::ApiError(e)),
-                    }
+        match client.get(&self.repo_route("")).await {
+            Err(PullRequestError::NotFound(_)) => Err(PullRequestError::RepoNotFound(format!(
+                "{}/{}",
+                self.repo_owner, self.repo_name
+            ))),
+            result => result,
+        }
+    }
+
+    /// The login of the user the token belongs to.
+    pub async fn fetch_token_user(&self) -> Result<String, PullRequestError> {
+        let client = self.client()?;
+
+        let user: Author = client.get("/user").await?;
+        Ok(user.login)
+    }
+
+    /// Looks up who the token belongs to unless that is already known.
+    async fn lookup_token_user(&self) -> Option<Result<String, String>> {
+        if matches!(self.token_user, Some(Ok(_))) {
+            return None;
+        }
+        Some(
+            self.fetch_token_user()
+                .await
+                .map_err(|e| self.error_message(&e)),
+        )
+    }
+
+    /// Every repository the token can reach, most recently pushed first.
+    pub async fn fetch_github_repositories(&self) -> Result<Vec<Repository>, PullRequestError> {
+        let client = self.client()?;
+
+        client.get_all("/user/repos?sort=pushed&per_page=100").await
+    }
+
+    /// Unread notifications about pull requests and failed workflow runs,
+    /// grouped for the inbox.
+    pub async fn fetch_notifications(&self) -> Result<Vec<InboxItem>, PullRequestError> {
+        let client = self.client()?;
+
+        let notifications: Vec<Notification> = client.get_all("/notifications?per_page=50").await?;
+        let mut items: Vec<InboxItem> = notifications
+            .iter()
+            .filter_map(InboxItem::from_notification)
+            .collect();
+        sort_inbox(&mut items);
+        Ok(items)
+    }
+
+    pub async fn mark_notification_read(&self, thread_id: &str) -> Result<(), PullRequestError> {
+        let client = self.client()?;
+
+        let _: Value = client
+            .patch(&format!("/notifications/threads/{}", thread_id), &json!({}))
+            .await?;
+        Ok(())
+    }
+
+    /// Open pull requests across all repositories that involve the user:
+    /// authored, awaiting their review, assigned, or requested from one of
+    /// their teams, with checks and review state.
+    pub async fn fetch_dashboard(&self) -> Result<Dashboard, PullRequestError> {
+        let client = self.client()?;
+
+        let mut searches = vec![
+            Involvement::Authored,
+            Involvement::ReviewRequested,
+            Involvement::Assigned,
+        ];
+        // Listing teams needs the read:org scope, without it the team
+        // searches are skipped.
+        let teams: Vec<UserTeam> = client
+            .get("/user/teams?per_page=100")
+            .await
+            .unwrap_or_default();
+        searches.extend(
+            teams.iter().take(DASHBOARD_MAX_TEAMS).map(|team| {
+                Involvement::Team(format!("{}/{}", team.organization.login, team.slug))
+            }),
+        );
+
+        // The searches run at once, and a failed one only leaves its own
+        // pull requests out.
+        let mut pending = JoinSet::new();
+        for (index, involvement) in searches.iter().enumerate() {
+            let client = client.clone();
+            let route = format!(
+                "/search/issues?q={}&sort=created&per_page={}",
+                encode_query(&involvement.query()),
+                DASHBOARD_SEARCH_LIMIT
+            );
+            pending.spawn(async move { (index, client.get::<SearchResults>(&route).await) });
+        }
+        let search_count = searches.len();
+        let mut results: Vec<_> = searches.iter().map(|_| None).collect();
+        while let Some(search) = pending.join_next().await {
+            if let Ok((index, result)) = search {
+                results[index] = Some(result);
+            }
+        }
+        let mut entries = Vec::new();
+        let mut failures = Vec::new();
+        let mut first_error = None;
+        for (involvement, result) in searches.into_iter().zip(results) {
+            match result {
+                Some(Ok(results)) => merge_results(&mut entries, involvement, &results.items),
+                Some(Err(e)) => {
+                    failures.push(format!(
+                        "Failed to search the {} pull requests: {}",
+                        involvement.label(),
+                        self.error_message(&e)
+                    ));
+                    first_error.get_or_insert(e);
+                }
+                None => {}
+            }
+        }
+        // With every search failed there is no dashboard to show.
+        if let Some(e) = first_error.filter(|_| failures.len() == search_count) {
+            return Err(e);
+        }
+
+        // One failed lookup only leaves its own entry without a status.
+        let permits = Arc::new(Semaphore::new(DASHBOARD_LOOKUPS));
+        let mut lookups = JoinSet::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let (client, permits) = (client.clone(), permits.clone());
+            let (repository, number) = (entry.repository.clone(), entry.number);
+            lookups.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                (index, entry_status(&client, &repository, number).await)
+            });
+        }
+        while let Some(lookup) = lookups.join_next().await {
+            if let Ok((index, status)) = lookup {
+                entries[index].set_status(status.map_err(|e| self.error_message(&e)));
+            }
+        }
+        sort_dashboard(&mut entries);
+        Ok(Dashboard { entries, failures })
+    }
+
+    /// Comments, reviews, review comment threads, commits and events of
+    /// pull request `number`, oldest first.
+    pub async fn fetch_timeline(
+        &self,
+        number: u64,
+    ) -> Result<Vec<TimelineEntry>, PullRequestError> {
+        let client = self.client()?;
+
+        let events: Vec<Value> = client
+            .get_all(&self.repo_route(&format!("/issues/{}/timeline?per_page=100", number)))
+            .await?;
+        let comments: Vec<Value> = client
+            .get_all(&self.repo_route(&format!("/pulls/{}/comments?per_page=100", number)))
+            .await?;
+        let mut threads = HashMap::new();
+        let mut after: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let response = client
+                .graphql(
+                    REVIEW_THREADS_QUERY,
+                    json!({
+                        "owner": self.repo_owner,
+                        "name": self.repo_name,
+                        "number": number,
+                        "after": after,
+                    }),
+                )
+                .await?;
+            threads.extend(review_threads(&response));
+            after = next_threads_cursor(&response);
+            if after.is_none() {
+                break;
+            }
+        }
+
+        let entries = events
+            .iter()
+            .filter_map(TimelineEntry::from_timeline_event)
+            .collect();
+        let review_comments = comments
+            .iter()
+            .filter_map(|comment| TimelineEntry::from_review_comment(comment, &threads))
+            .collect();
+        Ok(build_timeline(entries, review_comments))
+    }
+
+    /// Replies in the thread of a review comment, or adds a comment to the
+    /// conversation of pull request `number`.
+    pub async fn reply_to_timeline(
+        &self,
+        number: u64,
+        entry: Option<&TimelineEntry>,
+        body: &str,
+    ) -> Result<(), PullRequestError> {
+        let client = self.client()?;
+        if body.trim().is_empty() {
+            return Err(PullRequestError::InvalidInput("Reply is empty".to_string()));
+        }
+
+        let route = match entry.map(|entry| &entry.kind) {
+            Some(TimelineKind::ReviewComment { root_id, .. }) => {
+                format!("/pulls/{}/comments/{}/replies", number, root_id)
+            }
+            _ => format!("/issues/{}/comments", number),
+        };
+        let _: Value = client
+            .post(&self.repo_route(&route), &json!({ "body": body }))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn add_reaction(
+        &self,
+        entry: &TimelineEntry,
+        content: &str,
+    ) -> Result<(), PullRequestError> {
+        let client = self.client()?;
+
+        let route = match (entry.id, &entry.kind) {
+            (Some(id), TimelineKind::Comment) => format!("/issues/comments/{}/reactions", id),
+            (Some(id), TimelineKind::ReviewComment { .. }) => {
+                format!("/pulls/comments/{}/reactions", id)
+            }
+            _ => {
+                return Err(PullRequestError::InvalidInput(
+                    "Only comments can have reactions".to_string(),
+                ))
+            }
+        };
+        let _: Value = client
+            .post(&self.repo_route(&route), &json!({ "content": content }))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_review_thread_resolved(
+        &self,
+        thread: &ReviewThread,
+        resolved: bool,
+    ) -> Result<(), PullRequestError> {
+        let client = self.client()?;
+
+        let mutation = if resolved {
+            RESOLVE_THREAD_MUTATION
+        } else {
+            UNRESOLVE_THREAD_MUTATION
+        };
+        client.graphql(mutation, json!({ "id": thread.id })).await?;
+        Ok(())
+    }
+
+    pub fn selected_timeline_entry(&self) -> Option<&TimelineEntry> {
+        self.timeline.get(self.selected_timeline_entry)
+    }
+
+    pub fn select_next_timeline_entry(&mut self) {
+        if self.selected_timeline_entry + 1 < self.timeline.len() {
+            self.selected_timeline_entry += 1;
+        }
+    }
+
+    pub fn select_previous_timeline_entry(&mut self) {
+        self.selected_timeline_entry = self.selected_timeline_entry.saturating_sub(1);
+    }
+
+    pub fn selected_dashboard_entry(&self) -> Option<&DashboardEntry> {
+        self.dashboard.get(self.selected_dashboard_entry)
+    }
+
+    pub fn select_next_dashboard_entry(&mut self) {
+        if !self.dashboard.is_empty() {
+            self.selected_dashboard_entry =
+                (self.selected_dashboard_entry + 1) % self.dashboard.len();
+        }
+    }
+
+    pub fn select_previous_dashboard_entry(&mut self) {
+        if !self.dashboard.is_empty() {
+            let len = self.dashboard.len();
+            self.selected_dashboard_entry = (self.selected_dashboard_entry + len - 1) % len;
+        }
+    }
+
+    /// Whether the open dashboard should start its periodic refresh.
+    pub fn dashboard_refresh_due(&self, now: Instant) -> bool {
+        self.screen == Screen::Dashboard
+            && !self.dashboard_loading
+            && self
+                .dashboard_refreshed_at
+                .is_none_or(|at| now.duration_since(at) >= DASHBOARD_REFRESH)
+    }
+
+    pub fn selected_notification(&self) -> Option<&InboxItem> {
+        self.notifications.get(self.selected_notification)
+    }
+
+    pub fn select_next_notification(&mut self) {
+        if !self.notifications.is_empty() {
+            self.selected_notification =
+                (self.selected_notification + 1) % self.notifications.len();
+        }
+    }
+
+    pub fn select_previous_notification(&mut self) {
+        if !self.notifications.is_empty() {
+            let len = self.notifications.len();
+            self.selected_notification = (self.selected_notification + len - 1) % len;
+        }
+    }
+
+    /// Drops a thread from the inbox once it has been marked read.
+    pub fn remove_notification(&mut self, thread_id: &str) {
+        self.notifications
+            .retain(|item| item.thread_id != thread_id);
+        self.selected_notification = self
+            .selected_notification
+            .min(self.notifications.len().saturating_sub(1));
+    }
+
+    pub fn has_repository(&self) -> bool {
+        self.repo_owner != "-" && self.repo_name != "-"
+    }
+
+    pub fn set_repository(&mut self, owner: String, name: String) {
+        self.repo_owner = owner;
+        self.repo_name = name;
+        self.github_repository = GithubRepository::new();
+        self.current_pull_request = None;
+        self.opened_pull_request = None;
+        self.pull_request_status = None;
+    }
+
+    pub fn repository_url(&self) -> String {
+        let url = self.github_repository.get_url();
+        if url.is_empty() {
+            format!("{}/{}/{}", self.web_url(), self.repo_owner, self.repo_name)
+        } else {
+            url.trim_end_matches('/').to_string()
+        }
+    }
+
+    /// The web address of the GitHub instance the API URL points to.
+    pub fn web_url(&self) -> String {
+        web_url(&self.api_url)
+    }
+
+    pub fn compare_url(&self) -> String {
+        format!(
+            "{}/compare/{}...{}?expand=1",
+            self.repository_url(),
+            encode_path(&self.form.value(Field::TargetBranch)),
+            encode_path(&self.form.value(Field::SourceBranch))
+        )
+    }
+
+    pub fn pull_request_url(&self) -> Option<String> {
+        self.current_pull_request
+            .as_ref()
+            .and_then(|pr| pr.html_url.as_ref())
+            .map(|url| url.to_string())
+    }
+
+    pub fn select_next_repository(&mut self) {
+        if !self.repositories.is_empty() {
+            self.selected_repository = (self.selected_repository + 1) % self.repositories.len();
+        }
+    }
+
+    pub fn select_previous_repository(&mut self) {
+        if !self.repositories.is_empty() {
+            let len = self.repositories.len();
+            self.selected_repository = (self.selected_repository + len - 1) % len;
+        }
+    }
+
+    pub fn choose_selected_repository(&mut self) -> bool {
+        let Some((owner, name)) = self
+            .repositories
+            .get(self.selected_repository)
+            .and_then(|full_name| full_name.split_once('/'))
+            .map(|(owner, name)| (owner.to_string(), name.to_string()))
+        else {
+            return false;
+        };
+        self.set_repository(owner, name);
+        self.show_repo_popup = false;
+        true
+    }
+
+    pub fn checks(&self) -> &[Check] {
+        self.pull_request_status
+            .as_ref()
+            .map(|status| status.runs.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn selected_check(&self) -> Option<&Check> {
+        let checks = self.checks();
+        checks.get(self.selected_check.min(checks.len().saturating_sub(1)))
+    }
+
+    pub fn selected_check_mut(&mut self) -> Option<&mut Check> {
+        let runs = &mut self.pull_request_status.as_mut()?.runs;
+        let index = self.selected_check.min(runs.len().saturating_sub(1));
+        runs.get_mut(index)
+    }
+
+    pub fn select_next_check(&mut self) {
+        let len = self.checks().len();
+        if len > 0 {
+            self.selected_check = (self.selected_check.min(len - 1) + 1) % len;
+        }
+    }
+
+    pub fn select_previous_check(&mut self) {
+        let len = self.checks().len();
+        if len > 0 {
+            self.selected_check = (self.selected_check.min(len - 1) + len - 1) % len;
+        }
+    }
+
+    /// Keeps the help overlay from scrolling past its last line, `max` as
+    /// measured by the last frame.
+    pub fn clamp_help_scroll(&mut self, max: u16) {
+        self.help_scroll = self.help_scroll.min(max);
+    }
+
+    /// Scrolls whatever is in front by `lines`, down when positive: the
+    /// list of a popup or screen, the comparison in the confirmation popup or
+    /// the description of the form.
+    pub fn scroll(&mut self, lines: i16) {
+        let down = lines > 0;
+        if self.show_pat_popup || self.show_exit_popup {
+            return;
+        }
+        if self.show_help {
+            // Capped once the next frame knows how many lines there are.
+            self.help_scroll = self.help_scroll.saturating_add_signed(lines);
+            return;
+        }
+        if let Some(ref mut palette) = self.palette {
+            if down {
+                palette.select_next();
+            } else {
+                palette.select_previous();
+            }
+            return;
+        }
+        if self.show_repo_popup {
+            if down {
+                self.select_next_repository();
+            } else {
+                self.select_previous_repository();
+            }
+            return;
+        }
+        if self.show_confirm_popup {
+            let max = self.comparison.as_ref().map_or(0, |comparison| {
+                comparison.files.len() + comparison.warnings().len() + 3
+            });
+            let scroll = (self.confirm_scroll as i64 + lines as i64).clamp(0, max as i64);
+            self.confirm_scroll = scroll as u16;
+            return;
+        }
+        match self.screen {
+            Screen::Notifications if down => self.select_next_notification(),
+            Screen::Notifications => self.select_previous_notification(),
+            Screen::Dashboard if down => self.select_next_dashboard_entry(),
+            Screen::Dashboard => self.select_previous_dashboard_entry(),
+            Screen::Timeline if self.composing_reply => {}
+            Screen::Timeline if down => self.select_next_timeline_entry(),
+            Screen::Timeline => self.select_previous_timeline_entry(),
+            Screen::Main if self.is_showing_pull_request() => {
+                if down {
+                    self.select_next_check();
                 } else {
-                    Err(PullRequestError::ApiError(e))
+                    self.select_previous_check();
                 }
             }
+            Screen::Messages if down => self.messages.scroll_down(lines as usize),
+            Screen::Messages => self.messages.scroll_up(lines.unsigned_abs() as usize),
+            Screen::Main => self.form.scroll(Field::Description, lines),
         }
     }
+
     pub fn is_editing_description(&self) -> bool {
-        self.current_field == 1
+        self.field() == Field::Description
     }
 }
+
+/// The checks of the head commit of `pr` and its review state, read from
+/// `repo_route`, the route of the repository `pr` targets.
+async fn pull_request_status(
+    client: &GithubClient,
+    repo_route: &str,
+    pr: &OctocrabPullRequest,
+) -> Result<PullRequestStatus, PullRequestError> {
+    let sha = &pr.head.sha;
+
+    let combined: CombinedStatus = client
+        .get(&format!("{}/commits/{}/status", repo_route, sha))
+        .await?;
+    let check_runs: Vec<CheckRun> = client
+        .get_pages::<ListCheckRuns>(&format!(
+            "{}/commits/{}/check-runs?per_page=100",
+            repo_route, sha
+        ))
+        .await?
+        .into_iter()
+        .flat_map(|page| page.check_runs)
+        .collect();
+    let reviews: Vec<Review> = client
+        .get_all(&format!(
+            "{}/pulls/{}/reviews?per_page=100",
+            repo_route, pr.number
+        ))
+        .await?;
+
+    let statuses: Vec<_> = combined
+        .statuses
+        .iter()
+        .map(|status| status.state)
+        .collect();
+    let conclusions: Vec<_> = check_runs
+        .iter()
+        .map(|run| run.conclusion.clone())
+        .collect();
+    let now = Utc::now();
+    let runs = combined
+        .statuses
+        .iter()
+        .map(Check::from_status)
+        .chain(check_runs.iter().map(|run| Check::from_check_run(run, now)))
+        .collect();
+    let reviews: Vec<_> = reviews
+        .into_iter()
+        .filter_map(|review| Some((review.user?.login, review.state?)))
+        .collect();
+    let review_requested = pr
+        .requested_reviewers
+        .as_ref()
+        .is_some_and(|reviewers| !reviewers.is_empty())
+        || pr
+            .requested_teams
+            .as_ref()
+            .is_some_and(|teams| !teams.is_empty());
+
+    Ok(PullRequestStatus {
+        checks: ChecksState::from_results(&statuses, &conclusions),
+        review: ReviewDecision::from_reviews(&reviews, review_requested),
+        runs,
+    })
+}
+
+/// The checks and review of pull request `number` of `repository`.
+async fn entry_status(
+    client: &GithubClient,
+    repository: &str,
+    number: u64,
+) -> Result<PullRequestStatus, PullRequestError> {
+    let repo_route = format!("/repos/{}", repository);
+    let pr: OctocrabPullRequest = client
+        .get(&format!("{}/pulls/{}", repo_route, number))
+        .await?;
+    pull_request_status(client, &repo_route, &pr).await
+}
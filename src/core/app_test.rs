@@ -1,8 +1,18 @@
 #[cfg(test)]
 mod tests {
-    use crate::core::app::App;
+    use crate::core::app::{App, SyncResult};
+    use crate::core::checks::{Check, CheckOutcome};
+    use crate::core::pull_request::{ChecksState, PullRequestStatus, ReviewDecision};
+    use crate::core::clipboard::osc52_sequence;
+    use crate::core::field::Field;
     use crate::core::input_mode::InputMode;
+    use crate::core::keymap::Context;
+    use crate::core::screen::Screen;
     use crate::core::git::{get_current_branch, get_repo_info};
+    use crate::core::github::GithubRepository;
+    use crate::core::message_log::Severity;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::time::Instant;
 
     #[test]
     fn test_app_initialization() {
@@ -13,14 +23,14 @@ mod tests {
         let app = App::new();
 
         // Existing checks
-        assert_eq!(app.pull_request.target_branch, "main", "Default target branch should be main");
-        assert!(app.pull_request.title.is_empty(), "Initial PR title should be empty");
-        assert!(app.pull_request.description.is_empty(), "Initial PR description should be empty");
+        assert_eq!(app.form.value(Field::TargetBranch), "main", "Default target branch should be main");
+        assert!(app.form.value(Field::Title).is_empty(), "Initial PR title should be empty");
+        assert!(app.form.value(Field::Description).is_empty(), "Initial PR description should be empty");
 
         // Updated assertions for repo owner, name, and source branch
         assert_eq!(app.repo_owner, expected_owner, "Repo owner should match git config or default");
         assert_eq!(app.repo_name, expected_repo, "Repo name should match git config or default");
-        assert_eq!(app.pull_request.source_branch, expected_branch, "Source branch should match git branch or default");
+        assert_eq!(app.form.value(Field::SourceBranch), expected_branch, "Source branch should match git branch or default");
 
         // Other existing assertions
         assert_eq!(app.config_pat, "", "Initial config_pat should be an empty string");
@@ -29,9 +39,7 @@ mod tests {
         assert!(!app.show_confirm_popup, "show_confirm_popup should be false initially");
         assert!(!app.show_pat_popup, "show_pat_popup should be false initially");
         assert!(!app.show_exit_popup, "show_exit_popup should be false initially");
-        assert!(app.error_message.is_none(), "Initial error_message should be None");
-        assert!(app.success_message.is_none(), "Initial success_message should be None");
-        assert!(app.description_text_area.is_empty(), "Initial description_text_area should be empty");
+        assert!(app.messages.last_error().is_none(), "There should be no error initially");
         assert!(app.pat_input.is_empty(), "Initial pat_input should be empty");
     }
 
@@ -49,11 +57,10 @@ mod tests {
         let mut app = App::new();
 
         // Setup: Modify fields that should be reset
-        app.pull_request.title = "Test Title".to_string();
-        app.pull_request.description = "Test Description".to_string();
+        app.form.set(Field::Title, "Test Title");
+        app.form.set(Field::Description, "Test Description");
         app.set_error("An error occurred".to_string());
         app.set_success("Test Success".to_string());
-        app.description_text_area.insert_str("Initial text in textarea");
         app.input_mode = InputMode::Editing; // Change from default
         app.current_field = 1; // Change from default
         app.show_confirm_popup = true; // Change from default
@@ -62,14 +69,12 @@ mod tests {
         app.reset();
 
         // Assertions: Verify fields are reset to their default/initial states
-        assert!(app.pull_request.title.is_empty(), "PR Title should be empty after reset");
-        assert!(app.pull_request.description.is_empty(), "PR Description should be empty after reset");
-        assert!(app.error_message.is_none(), "Error message should be None after reset");
-        assert!(app.success_message.is_none(), "Success message should be None after reset");
+        assert!(app.form.value(Field::Title).is_empty(), "PR Title should be empty after reset");
+        assert!(app.form.value(Field::Description).is_empty(), "PR Description should be empty after reset");
+        assert!(app.messages.toast(Instant::now()).is_none(), "The message should be dismissed after reset");
         assert_eq!(app.input_mode, InputMode::Normal, "Input mode should be Normal after reset");
         assert_eq!(app.current_field, 0, "Current field should be 0 after reset");
         assert!(!app.show_confirm_popup, "Show confirm popup should be false after reset");
-        assert!(app.description_text_area.is_empty(), "Description textarea should be empty after reset");
     }
 
     #[test]
@@ -77,7 +82,9 @@ mod tests {
         let mut app = App::new();
         app.set_error("An error occurred".to_string());
 
-        assert_eq!(app.error_message, Some("An error occurred".to_string()));
+        let error = app.messages.last_error().unwrap();
+        assert_eq!(error.text, "An error occurred");
+        assert!(app.messages.toast(Instant::now()).is_some());
     }
 
     #[test]
@@ -85,10 +92,9 @@ mod tests {
         let mut app = App::new();
         app.set_success("Pull request created successfully".to_string());
 
-        assert_eq!(
-            app.success_message,
-            Some("Pull request created successfully".to_string())
-        );
+        let success = app.messages.last().unwrap();
+        assert_eq!(success.severity, Severity::Success);
+        assert_eq!(success.text, "Pull request created successfully");
     }
 
     #[test]
@@ -98,43 +104,27 @@ mod tests {
 
         app.clear_message();
 
-        assert!(app.success_message.is_none());
+        assert!(app.messages.toast(Instant::now()).is_none());
+        assert_eq!(app.messages.last().unwrap().text, "Success");
     }
 
     #[test]
-    fn test_get_current_field_mut_title() {
+    fn test_input_key_edits_the_focused_field() {
         let mut app = App::new();
-        app.current_field = 0;
-        let title_field = app.get_current_field_mut();
-        *title_field = "New Title".to_string();
-        assert_eq!(app.pull_request.title, "New Title");
-    }
+        app.form.set(Field::SourceBranch, "feature");
+        app.enter_edit_mode(Field::SourceBranch.index());
+        assert_eq!(app.field(), Field::SourceBranch);
 
-    #[test]
-    fn test_get_current_field_mut_description() {
-        let mut app = App::new();
-        app.current_field = 1;
-        let description_field = app.get_current_field_mut();
-        *description_field = "New Description".to_string();
-        assert_eq!(app.pull_request.description, "New Description");
-    }
+        app.input_key(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+        for c in "my-".chars() {
+            app.input_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert!(!app.input_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert_eq!(app.form.value(Field::SourceBranch), "my-feature");
 
-    #[test]
-    fn test_get_current_field_mut_source_branch() {
-        let mut app = App::new();
-        app.current_field = 2;
-        let source_branch_field = app.get_current_field_mut();
-        *source_branch_field = "feature/new-branch".to_string();
-        assert_eq!(app.pull_request.source_branch, "feature/new-branch");
-    }
-
-    #[test]
-    fn test_get_current_field_mut_target_branch() {
-        let mut app = App::new();
-        app.current_field = 3;
-        let target_branch_field = app.get_current_field_mut();
-        *target_branch_field = "develop".to_string();
-        assert_eq!(app.pull_request.target_branch, "develop");
+        app.current_field = Field::Title.index();
+        app.paste("Pasted\ntitle");
+        assert_eq!(app.form.value(Field::Title), "Pasted title");
     }
 
     #[test]
@@ -165,4 +155,276 @@ mod tests {
         app.current_field = 3;
         assert!(!app.is_editing_description(), "Should be false when current_field is 3");
     }
+
+    #[test]
+    fn test_set_repository() {
+        let mut app = App::new();
+        app.github_repository
+            .set_url("https://github.com/old/repo".to_string());
+        app.opened_pull_request = Some(7);
+
+        app.set_repository("djego".to_string(), "prt".to_string());
+
+        assert_eq!(app.repo_owner, "djego");
+        assert_eq!(app.repo_name, "prt");
+        assert!(app.has_repository());
+        assert!(app.github_repository.get_url().is_empty());
+        assert_eq!(app.opened_pull_request, None);
+    }
+
+    #[test]
+    fn test_choose_selected_repository() {
+        let mut app = App::new();
+        app.repositories = vec!["djego/prt".to_string(), "rust-lang/rust".to_string()];
+        app.show_repo_popup = true;
+
+        app.select_previous_repository();
+        assert_eq!(app.selected_repository, 1);
+        app.select_next_repository();
+        assert_eq!(app.selected_repository, 0);
+        app.select_next_repository();
+
+        assert!(app.choose_selected_repository());
+        assert_eq!(app.repo_owner, "rust-lang");
+        assert_eq!(app.repo_name, "rust");
+        assert!(!app.show_repo_popup);
+    }
+
+    #[test]
+    fn test_repository_and_compare_urls() {
+        let mut app = App::new();
+        app.set_repository("djego".to_string(), "prt".to_string());
+        app.form.set(Field::SourceBranch, "feature");
+        app.form.set(Field::TargetBranch, "main");
+
+        assert_eq!(app.repository_url(), "https://github.com/djego/prt");
+        assert_eq!(
+            app.compare_url(),
+            "https://github.com/djego/prt/compare/main...feature?expand=1"
+        );
+        app.form.set(Field::SourceBranch, "feature/#1 50%?");
+        assert_eq!(
+            app.compare_url(),
+            "https://github.com/djego/prt/compare/main...feature/%231%2050%25%3F?expand=1"
+        );
+
+        app.github_repository
+            .set_url("https://github.example.com/djego/prt/".to_string());
+        assert_eq!(app.repository_url(), "https://github.example.com/djego/prt");
+        assert_eq!(app.pull_request_url(), None);
+
+        app.github_repository = GithubRepository::new();
+        app.api_url = "https://ghe.example.com/api/v3".to_string();
+        assert_eq!(app.repository_url(), "https://ghe.example.com/djego/prt");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(
+            osc52_sequence("https://github.com"),
+            "\x1b]52;c;aHR0cHM6Ly9naXRodWIuY29t\x07"
+        );
+    }
+
+    #[test]
+    fn test_edit_existing_pull_request() {
+        let mut app = App::new();
+        app.form.set(Field::SourceBranch, "local");
+        app.form.set(Field::TargetBranch, "main");
+        app.current_pull_request = Some(
+            serde_json::from_value(serde_json::json!({
+                "url": "https://api.github.com/repos/djego/prt/pulls/7",
+                "id": 1,
+                "number": 7,
+                "title": "Add feature",
+                "body": "First line\nSecond line",
+                "html_url": "https://github.com/djego/prt/pull/7",
+                "head": { "ref": "feature", "sha": "abc" },
+                "base": { "ref": "develop", "sha": "def" }
+            }))
+            .unwrap(),
+        );
+        assert!(app.is_showing_pull_request());
+
+        app.edit_existing_pull_request();
+
+        assert!(!app.is_showing_pull_request());
+        assert_eq!(app.editing_pull_request, Some(7));
+        assert_eq!(app.input_mode, InputMode::Editing);
+        assert_eq!(app.form.value(Field::Title), "Add feature");
+        assert_eq!(app.form.value(Field::TargetBranch), "develop");
+        assert_eq!(
+            app.form.input(Field::Description).text(),
+            "First line\nSecond line"
+        );
+        assert_eq!(
+            app.pull_request_url().as_deref(),
+            Some("https://github.com/djego/prt/pull/7")
+        );
+
+        app.reset();
+        assert_eq!(app.editing_pull_request, None);
+        assert!(app.is_showing_pull_request());
+        assert_eq!(app.form.value(Field::SourceBranch), "local");
+        assert_eq!(app.form.value(Field::TargetBranch), "main");
+        assert_eq!(app.form.value(Field::Title), "");
+
+        // Editing twice still brings back the form from before the first.
+        app.edit_existing_pull_request();
+        app.edit_existing_pull_request();
+        app.reset();
+        assert_eq!(app.form.value(Field::SourceBranch), "local");
+    }
+
+    #[test]
+    fn test_select_checks() {
+        let mut app = App::new();
+        assert!(app.selected_check().is_none());
+        app.select_next_check();
+        assert_eq!(app.selected_check, 0);
+
+        let check = |name: &str, outcome| Check {
+            name: name.to_string(),
+            outcome,
+            duration_seconds: Some(30),
+            url: None,
+            job: None,
+            log_tail: None,
+        };
+        app.pull_request_status = Some(PullRequestStatus {
+            checks: ChecksState::Failure,
+            review: ReviewDecision::None,
+            runs: vec![
+                check("build", CheckOutcome::Success),
+                check("test", CheckOutcome::Failure),
+            ],
+        });
+
+        app.select_next_check();
+        assert_eq!(app.selected_check().unwrap().name, "test");
+        app.select_next_check();
+        assert_eq!(app.selected_check().unwrap().name, "build");
+        app.select_previous_check();
+        assert_eq!(app.selected_check().unwrap().name, "test");
+
+        app.selected_check_mut().unwrap().log_tail = Some(vec!["error".to_string()]);
+        assert_eq!(app.checks()[1].log_tail.as_deref(), Some(&["error".to_string()][..]));
+    }
+
+    #[test]
+    fn test_apply_editor_text() {
+        let mut app = App::new();
+        app.enter_edit_mode(0);
+        assert_eq!(app.editor_file_name(), Some("PR_TITLE"));
+        app.apply_editor_text("\n  Add editor support  \nignored".to_string());
+        assert_eq!(app.form.value(Field::Title), "Add editor support");
+
+        app.current_field = 1;
+        assert_eq!(app.editor_file_name(), Some("PR_DESCRIPTION.md"));
+        app.apply_editor_text("## Summary\n\nDetails".to_string());
+        assert_eq!(app.form.value(Field::Description), "## Summary\n\nDetails");
+
+        app.current_field = 2;
+        assert_eq!(app.editor_file_name(), None);
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut app = App::new();
+        app.current_pull_request = None;
+        app.form.set(Field::Description, "1\n2\n3\n4\n5");
+        app.enter_edit_mode(1);
+        app.scroll(-3);
+        app.input_key(KeyEvent::new(KeyCode::Char('!'), KeyModifiers::NONE));
+        assert_eq!(app.form.value(Field::Description), "1\n2!\n3\n4\n5");
+
+        app.repositories = vec!["a/one".to_string(), "a/two".to_string()];
+        app.show_repo_popup = true;
+        app.scroll(3);
+        assert_eq!(app.selected_repository, 1);
+        app.show_repo_popup = false;
+
+        app.confirm_pull_request();
+        app.scroll(-3);
+        assert_eq!(app.confirm_scroll, 0);
+    }
+
+    #[test]
+    fn test_context() {
+        let mut app = App::new();
+        assert_eq!(app.context(), Context::Normal);
+        app.enter_edit_mode(0);
+        assert_eq!(app.context(), Context::Editing);
+        app.screen = Screen::Dashboard;
+        assert_eq!(app.context(), Context::Dashboard);
+        app.screen = Screen::Timeline;
+        app.composing_reply = true;
+        assert_eq!(app.context(), Context::Reply);
+        assert_eq!(app.key_context(), Context::Reply);
+        app.show_help = true;
+        assert_eq!(app.context(), Context::Reply);
+        assert_eq!(app.key_context(), Context::Help);
+    }
+
+    fn sync(app: &App, default_branch: &str, number: Option<u64>) -> SyncResult {
+        SyncResult {
+            repo_owner: app.repo_owner.clone(),
+            repo_name: app.repo_name.clone(),
+            repository: serde_json::from_value(serde_json::json!({
+                "id": 1,
+                "name": "prt",
+                "url": "https://api.github.com/repos/djego/prt",
+                "default_branch": default_branch
+            }))
+            .unwrap(),
+            existing: number.map(|number| {
+                let pr = serde_json::from_value(serde_json::json!({
+                    "url": "https://api.github.com/repos/djego/prt/pulls/1",
+                    "id": number,
+                    "number": number,
+                    "head": { "ref": "feature", "sha": "abc" },
+                    "base": { "ref": "main", "sha": "def" }
+                }))
+                .unwrap();
+                let status = PullRequestStatus {
+                    checks: ChecksState::None,
+                    review: ReviewDecision::None,
+                    runs: Vec::new(),
+                };
+                (pr, status)
+            }),
+            opened_pull_request: app.opened_pull_request,
+            source_branch: app.form.value(Field::SourceBranch),
+            token_user: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_sync_keeps_what_the_user_changed() {
+        let mut app = App::new();
+        app.set_repository("djego".to_string(), "prt".to_string());
+        app.form.set(Field::SourceBranch, "feature");
+
+        assert!(app.apply_sync(sync(&app, "develop", Some(3))));
+        assert_eq!(app.form.value(Field::TargetBranch), "develop");
+        assert_eq!(app.current_pull_request.as_ref().map(|pr| pr.number), Some(3));
+
+        // A sync for the branch the user has since moved away from.
+        let stale = sync(&app, "develop", None);
+        app.enter_edit_mode(Field::SourceBranch.index());
+        app.input_key(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE));
+        app.enter_edit_mode(Field::TargetBranch.index());
+        app.input_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        assert!(app.apply_sync(stale));
+        assert_eq!(app.current_pull_request.as_ref().map(|pr| pr.number), Some(3));
+        assert_eq!(app.form.value(Field::TargetBranch), "develo");
+
+        assert!(app.apply_sync(sync(&app, "main", None)));
+        assert_eq!(app.current_pull_request.as_ref().map(|pr| pr.number), None);
+        assert_eq!(app.form.value(Field::TargetBranch), "develo");
+
+        app.opened_pull_request = Some(9);
+        assert!(app.apply_sync(sync(&app, "main", Some(9))));
+        assert_eq!(app.current_pull_request.as_ref().map(|pr| pr.number), Some(9));
+    }
 }
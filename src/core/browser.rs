@@ -0,0 +1,39 @@
+use std::env;
+use std::io;
+use std::process::{Command, Stdio};
+
+/// Opens `url` with `$BROWSER` when set, or the platform's default opener.
+pub fn open_url(url: &str) -> Result<(), io::Error> {
+    let browser = env::var("BROWSER").unwrap_or_default();
+    // $BROWSER may hold a colon-separated list of commands; use the first one.
+    let mut parts: Vec<String> = browser
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    if parts.is_empty() {
+        parts = default_opener();
+    }
+
+    Command::new(&parts[0])
+        .args(&parts[1..])
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+fn default_opener() -> Vec<String> {
+    let command: &[&str] = if cfg!(target_os = "macos") {
+        &["open"]
+    } else if cfg!(windows) {
+        &["cmd", "/C", "start", ""]
+    } else {
+        &["xdg-open"]
+    };
+    command.iter().map(|part| part.to_string()).collect()
+}
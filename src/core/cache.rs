@@ -0,0 +1,139 @@
+use crate::core::config::config_dir;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// The most responses kept on disk. The oldest go first.
+const MAX_ENTRIES: usize = 500;
+
+/// Responses larger than this are not kept, so a few big lists can't fill
+/// the disk.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CachedResponse {
+    pub key: String,
+    pub etag: String,
+    pub body: String,
+    /// The route of the next page of a list.
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+/// On-disk store of GET responses and their ETags, so repeated requests can
+/// be sent as conditional requests and answered with `304 Not Modified`.
+/// Responses may hold private repository data, so only the user can read
+/// them, and at most `max_entries` are kept.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    max_entries: usize,
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf) -> ResponseCache {
+        ResponseCache {
+            dir,
+            max_entries: MAX_ENTRIES,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_max_entries(mut self, max_entries: usize) -> ResponseCache {
+        self.max_entries = max_entries;
+        self
+    }
+
+    pub fn in_config_dir() -> ResponseCache {
+        ResponseCache::new(config_dir().join("cache"))
+    }
+
+    pub fn load(&self, key: &str) -> Option<CachedResponse> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        let cached: CachedResponse = serde_json::from_str(&content).ok()?;
+        (cached.key == key).then_some(cached)
+    }
+
+    pub fn store(&self, key: &str, etag: &str, body: &str, next: Option<&str>) -> io::Result<()> {
+        if body.len() > MAX_BODY_BYTES {
+            return Ok(());
+        }
+        let mut dir = DirBuilder::new();
+        dir.recursive(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            dir.mode(0o700);
+        }
+        dir.create(&self.dir)?;
+        let cached = CachedResponse {
+            key: key.to_string(),
+            etag: etag.to_string(),
+            body: body.to_string(),
+            next: next.map(str::to_string),
+        };
+        let path = self.path(key);
+        let tmp = path.with_extension("tmp");
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&tmp)?
+            .write_all(serde_json::to_string(&cached)?.as_bytes())?;
+        fs::rename(tmp, path)?;
+        self.prune()
+    }
+
+    /// Removes the oldest responses beyond `max_entries`.
+    fn prune(&self) -> io::Result<()> {
+        let mut entries: Vec<_> = fs::read_dir(&self.dir)?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .collect();
+        if entries.len() <= self.max_entries {
+            return Ok(());
+        }
+        entries.sort();
+        let excess = entries.len() - self.max_entries;
+        for (_, path) in entries.into_iter().take(excess) {
+            let _ = fs::remove_file(path);
+        }
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(key)))
+    }
+}
+
+/// Whether responses to `route` may be cached. Job logs are large and may
+/// hold secrets printed by a build, so they are always downloaded again.
+pub fn is_cacheable(route: &str) -> bool {
+    let path = route.split('?').next().unwrap_or(route);
+    !path.ends_with("/logs")
+}
+
+/// Cache key for a route, scoped to the API host and the token so that
+/// switching accounts never serves another account's responses.
+pub fn cache_key(base_uri: &str, pat: &str, route: &str) -> String {
+    format!(
+        "{:016x} {}{}",
+        fnv1a(pat),
+        base_uri.trim_end_matches('/'),
+        route
+    )
+}
+
+/// FNV-1a, used instead of `DefaultHasher` because file names must stay
+/// the same across builds.
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::cache::{cache_key, is_cacheable, ResponseCache};
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    fn temp_cache(name: &str) -> ResponseCache {
+        let dir = env::temp_dir().join(format!("prt-cache-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        ResponseCache::new(dir)
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let cache = temp_cache("roundtrip");
+        let key = cache_key("https://api.github.com", "token", "/repos/djego/prt");
+
+        assert_eq!(cache.load(&key), None);
+        cache
+            .store(&key, "\"abc\"", r#"{"name":"prt"}"#, Some("/page/2"))
+            .unwrap();
+
+        let cached = cache.load(&key).unwrap();
+        assert_eq!(cached.etag, "\"abc\"");
+        assert_eq!(cached.body, r#"{"name":"prt"}"#);
+        assert_eq!(cached.next.as_deref(), Some("/page/2"));
+
+        cache.store(&key, "\"def\"", "{}", None).unwrap();
+        assert_eq!(cache.load(&key).unwrap().etag, "\"def\"");
+    }
+
+    #[test]
+    fn test_store_is_bounded() {
+        let cache = temp_cache("bounded").with_max_entries(2);
+        let key = |route| cache_key("https://api.github.com", "token", route);
+        for route in ["/a", "/b", "/c"] {
+            cache.store(&key(route), "\"e\"", "{}", None).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert_eq!(cache.load(&key("/a")), None);
+        assert!(cache.load(&key("/b")).is_some());
+        assert!(cache.load(&key("/c")).is_some());
+
+        let huge = "x".repeat(2 * 1024 * 1024);
+        cache.store(&key("/huge"), "\"e\"", &huge, None).unwrap();
+        assert_eq!(cache.load(&key("/huge")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_store_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let cache = temp_cache("private");
+        let key = cache_key("https://api.github.com", "token", "/repos/djego/prt");
+        cache.store(&key, "\"e\"", "{}", None).unwrap();
+        let dir = env::temp_dir().join(format!("prt-cache-private-{}", process::id()));
+        let file = fs::read_dir(&dir).unwrap().next().unwrap().unwrap();
+        assert_eq!(file.metadata().unwrap().permissions().mode() & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_logs_are_not_cacheable() {
+        assert!(is_cacheable("/repos/djego/prt/pulls?per_page=100"));
+        assert!(!is_cacheable("/repos/djego/prt/actions/jobs/1/logs"));
+        assert!(!is_cacheable("/repos/djego/prt/actions/jobs/1/logs?raw=1"));
+    }
+
+    #[test]
+    fn test_keys_are_scoped_to_token_and_host() {
+        let route = "/repos/djego/prt";
+        let key = cache_key("https://api.github.com", "token", route);
+        assert_ne!(key, cache_key("https://api.github.com", "other", route));
+        assert_ne!(
+            key,
+            cache_key("https://ghe.example.com/api/v3", "token", route)
+        );
+        assert_eq!(key, cache_key("https://api.github.com/", "token", route));
+        assert!(!key.contains("token"));
+    }
+}
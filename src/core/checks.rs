@@ -0,0 +1,188 @@
+use chrono::{DateTime, Utc};
+use octocrab::models::checks::CheckRun;
+use octocrab::models::{Status, StatusState};
+use serde::Serialize;
+
+/// Number of log lines shown under an expanded failed check.
+pub const LOG_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckOutcome {
+    Pending,
+    Success,
+    Failure,
+    Cancelled,
+    Skipped,
+    Neutral,
+}
+
+impl CheckOutcome {
+    pub fn from_status(state: StatusState) -> CheckOutcome {
+        match state {
+            StatusState::Success => CheckOutcome::Success,
+            StatusState::Failure | StatusState::Error => CheckOutcome::Failure,
+            _ => CheckOutcome::Pending,
+        }
+    }
+
+    /// Maps a check run conclusion, which is empty while the run is queued
+    /// or in progress.
+    pub fn from_conclusion(conclusion: Option<&str>) -> CheckOutcome {
+        match conclusion {
+            None => CheckOutcome::Pending,
+            Some("success") => CheckOutcome::Success,
+            Some("cancelled") => CheckOutcome::Cancelled,
+            Some("skipped") => CheckOutcome::Skipped,
+            Some("neutral") | Some("stale") => CheckOutcome::Neutral,
+            Some(_) => CheckOutcome::Failure,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CheckOutcome::Pending => "●",
+            CheckOutcome::Success => "✔",
+            CheckOutcome::Failure => "✘",
+            CheckOutcome::Cancelled => "⊘",
+            CheckOutcome::Skipped | CheckOutcome::Neutral => "-",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckOutcome::Pending => "pending",
+            CheckOutcome::Success => "success",
+            CheckOutcome::Failure => "failure",
+            CheckOutcome::Cancelled => "cancelled",
+            CheckOutcome::Skipped => "skipped",
+            CheckOutcome::Neutral => "neutral",
+        }
+    }
+}
+
+/// The GitHub Actions job behind a check run, needed to fetch its log and
+/// re-run it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActionsJob {
+    pub run_id: u64,
+    pub job_id: u64,
+}
+
+impl ActionsJob {
+    /// Parses `https://github.com/{owner}/{repo}/actions/runs/{run}/job/{job}`.
+    pub fn from_details_url(url: &str) -> Option<ActionsJob> {
+        let (_, path) = url.split_once("/actions/runs/")?;
+        let mut parts = path.split('/');
+        let run_id = parts.next()?.parse().ok()?;
+        if parts.next()? != "job" {
+            return None;
+        }
+        let job_id = parts.next()?.split(['?', '#']).next()?.parse().ok()?;
+        Some(ActionsJob { run_id, job_id })
+    }
+}
+
+/// A commit status or check run reported for the head of a pull request.
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub outcome: CheckOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip)]
+    pub job: Option<ActionsJob>,
+    #[serde(skip)]
+    pub log_tail: Option<Vec<String>>,
+}
+
+impl Check {
+    pub fn from_status(status: &Status) -> Check {
+        Check {
+            name: status.context.clone().unwrap_or_default(),
+            outcome: CheckOutcome::from_status(status.state),
+            duration_seconds: None,
+            url: status.target_url.clone(),
+            job: None,
+            log_tail: None,
+        }
+    }
+
+    /// Runs that are still going are timed up to `now`.
+    pub fn from_check_run(run: &CheckRun, now: DateTime<Utc>) -> Check {
+        let duration_seconds = run
+            .started_at
+            .map(|started| (run.completed_at.unwrap_or(now) - started).num_seconds());
+        Check {
+            name: run.name.clone(),
+            outcome: CheckOutcome::from_conclusion(run.conclusion.as_deref()),
+            duration_seconds,
+            url: run.html_url.clone().or_else(|| run.details_url.clone()),
+            job: run
+                .details_url
+                .as_deref()
+                .and_then(ActionsJob::from_details_url),
+            log_tail: None,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.outcome == CheckOutcome::Failure
+    }
+
+    pub fn duration_label(&self) -> String {
+        self.duration_seconds
+            .map(format_duration)
+            .unwrap_or_default()
+    }
+}
+
+pub fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+/// Last `count` lines of a job log, without the timestamp GitHub Actions
+/// puts in front of each line or terminal colour codes.
+pub fn log_tail(log: &str, count: usize) -> Vec<String> {
+    let lines: Vec<String> = log
+        .lines()
+        .map(|line| strip_ansi(strip_timestamp(line)))
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(count)..].to_vec()
+}
+
+fn strip_timestamp(line: &str) -> &str {
+    match line.split_once(' ') {
+        Some((stamp, rest))
+            if stamp.len() >= 20 && stamp.ends_with('Z') && stamp.as_bytes()[10] == b'T' =>
+        {
+            rest
+        }
+        _ => line,
+    }
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            text.push(c);
+        }
+    }
+    text
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::checks::{format_duration, log_tail, ActionsJob, CheckOutcome};
+    use octocrab::models::StatusState;
+
+    #[test]
+    fn test_outcome_from_conclusion() {
+        assert_eq!(CheckOutcome::from_conclusion(None), CheckOutcome::Pending);
+        assert_eq!(
+            CheckOutcome::from_conclusion(Some("success")),
+            CheckOutcome::Success
+        );
+        assert_eq!(
+            CheckOutcome::from_conclusion(Some("timed_out")),
+            CheckOutcome::Failure
+        );
+        assert_eq!(
+            CheckOutcome::from_conclusion(Some("skipped")),
+            CheckOutcome::Skipped
+        );
+        assert_eq!(
+            CheckOutcome::from_status(StatusState::Error),
+            CheckOutcome::Failure
+        );
+    }
+
+    #[test]
+    fn test_actions_job_from_details_url() {
+        assert_eq!(
+            ActionsJob::from_details_url(
+                "https://github.com/djego/prt/actions/runs/123/job/456?pr=7"
+            ),
+            Some(ActionsJob {
+                run_id: 123,
+                job_id: 456
+            })
+        );
+        assert_eq!(
+            ActionsJob::from_details_url("https://ci.example.com/builds/42"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(65), "1m 05s");
+        assert_eq!(format_duration(3720), "1h 02m");
+        assert_eq!(format_duration(-3), "0s");
+    }
+
+    #[test]
+    fn test_log_tail() {
+        let log = "2024-05-01T10:00:00.0000000Z Compiling prt\n\
+                   2024-05-01T10:00:01.0000000Z \x1b[31merror\x1b[0m: mismatched types\n\
+                   \n\
+                   2024-05-01T10:00:02.0000000Z ##[error]Process completed with exit code 101.";
+        assert_eq!(
+            log_tail(log, 2),
+            vec![
+                "error: mismatched types".to_string(),
+                "##[error]Process completed with exit code 101.".to_string(),
+            ]
+        );
+        assert_eq!(log_tail(log, 10).len(), 3);
+    }
+}
@@ -0,0 +1,470 @@
+use crate::core::cache::{cache_key, is_cacheable, ResponseCache};
+use crate::core::errors::{decode_github_error, PullRequestError};
+use chrono::Local;
+use http::header::{HeaderMap, ACCEPT, CONTENT_TYPE, ETAG, IF_NONE_MATCH, LINK, RETRY_AFTER};
+use http::{Method, Request, StatusCode};
+use octocrab::service::middleware::retry::RetryConfig;
+use octocrab::Octocrab;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// The most pages read from one list, so a huge list can't keep a screen
+/// loading forever.
+pub const MAX_PAGES: usize = 20;
+
+/// GitHub's documented minimum wait after a secondary rate limit response
+/// that does not say how long to wait.
+const SECONDARY_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset: u64,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &HeaderMap) -> Option<RateLimit> {
+        Some(RateLimit {
+            limit: header_u64(headers, "x-ratelimit-limit")?,
+            remaining: header_u64(headers, "x-ratelimit-remaining")?,
+            reset: header_u64(headers, "x-ratelimit-reset").unwrap_or_default(),
+        })
+    }
+
+    pub fn seconds_until_reset(&self) -> u64 {
+        self.reset.saturating_sub(unix_now())
+    }
+}
+
+pub type SharedRateLimit = Arc<Mutex<Option<RateLimit>>>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// The longest advertised rate limit wait that is waited out; longer
+    /// ones are reported instead of blocking the caller.
+    pub max_rate_limit_wait: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(4),
+            max_rate_limit_wait: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait before retrying a request that got `status`, or
+    /// `None` when it must not be retried. Rate limited requests were never
+    /// processed, so they are retried whatever the method once the advertised
+    /// wait is over, unless it is longer than `max_rate_limit_wait`. Server
+    /// errors are only retried for idempotent requests, after a short backoff.
+    pub fn delay(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+        idempotent: bool,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        if is_rate_limited(status, headers) {
+            let wait = rate_limit_wait(headers);
+            return (wait <= self.max_rate_limit_wait).then_some(wait);
+        }
+        if idempotent && status.is_server_error() {
+            return Some(self.backoff(attempt));
+        }
+        None
+    }
+
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay)
+    }
+}
+
+/// Shared HTTP layer for every GitHub call. It records the rate limit
+/// headers of each response and retries what is safe to retry; octocrab's
+/// own retry is disabled because it would also repeat creates. With a
+/// cache, reads are sent as conditional requests, and a `304 Not Modified`
+/// does not count against the rate limit.
+#[derive(Clone)]
+pub struct GithubClient {
+    octocrab: Octocrab,
+    base_uri: String,
+    rate_limit: SharedRateLimit,
+    retry: RetryPolicy,
+    cache: Option<ResponseCache>,
+    cache_scope: String,
+    cache_only: bool,
+}
+
+impl GithubClient {
+    pub fn new(
+        pat: &str,
+        base_uri: &str,
+        rate_limit: SharedRateLimit,
+    ) -> Result<GithubClient, PullRequestError> {
+        let octocrab = Octocrab::builder()
+            .personal_token(pat.to_string())
+            .base_uri(base_uri)?
+            .add_retry_config(RetryConfig::None)
+            .build()?;
+        Ok(GithubClient {
+            octocrab,
+            base_uri: base_uri.trim_end_matches('/').to_string(),
+            rate_limit,
+            retry: RetryPolicy::default(),
+            cache: None,
+            cache_scope: cache_key(base_uri, pat, ""),
+            cache_only: false,
+        })
+    }
+
+    pub fn with_cache(mut self, cache: ResponseCache) -> GithubClient {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Answers reads from the cache alone, without touching the network.
+    pub fn cache_only(mut self) -> GithubClient {
+        self.cache_only = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> GithubClient {
+        self.retry = retry;
+        self
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, route: &str) -> Result<T, PullRequestError> {
+        self.request(Method::GET, route, None::<&()>).await
+    }
+
+    /// Reads every item of a list, following the `next` links.
+    pub async fn get_all<T: DeserializeOwned>(
+        &self,
+        route: &str,
+    ) -> Result<Vec<T>, PullRequestError> {
+        let pages: Vec<Vec<T>> = self.get_pages(route).await?;
+        Ok(pages.into_iter().flatten().collect())
+    }
+
+    /// Reads every page of a paginated resource, such as the check runs of
+    /// a commit, following the `next` links up to `MAX_PAGES` pages.
+    pub async fn get_pages<T: DeserializeOwned>(
+        &self,
+        route: &str,
+    ) -> Result<Vec<T>, PullRequestError> {
+        let mut pages = Vec::new();
+        let mut next = Some(route.to_string());
+        while let Some(route) = next.take().filter(|_| pages.len() < MAX_PAGES) {
+            let (text, link) = self.fetch(Method::GET, &route, None::<&()>).await?;
+            pages.push(parse_body(&text)?);
+            next = link;
+        }
+        Ok(pages)
+    }
+
+    pub async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        route: &str,
+        body: &B,
+    ) -> Result<T, PullRequestError> {
+        self.request(Method::POST, route, Some(body)).await
+    }
+
+    pub async fn patch<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        route: &str,
+        body: &B,
+    ) -> Result<T, PullRequestError> {
+        self.request(Method::PATCH, route, Some(body)).await
+    }
+
+    pub async fn put<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        route: &str,
+        body: &B,
+    ) -> Result<T, PullRequestError> {
+        self.request(Method::PUT, route, Some(body)).await
+    }
+
+    /// Runs a GraphQL query or mutation, turning the errors GraphQL reports
+    /// alongside a `200 OK` into an error.
+    pub async fn graphql(&self, query: &str, variables: Value) -> Result<Value, PullRequestError> {
+        let response: Value = self
+            .post(
+                "/graphql",
+                &json!({ "query": query, "variables": variables }),
+            )
+            .await?;
+        match response.get("errors").and_then(Value::as_array) {
+            Some(errors) if !errors.is_empty() => {
+                let messages: Vec<&str> = errors
+                    .iter()
+                    .filter_map(|error| error.get("message").and_then(Value::as_str))
+                    .collect();
+                Err(PullRequestError::ApiError(messages.join(", ")))
+            }
+            _ => Ok(response),
+        }
+    }
+
+    /// Reads a plain text resource, such as a job log.
+    pub async fn get_text(&self, route: &str) -> Result<String, PullRequestError> {
+        self.request_text(Method::GET, route, None::<&()>).await
+    }
+
+    async fn request<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        route: &str,
+        body: Option<&B>,
+    ) -> Result<T, PullRequestError> {
+        parse_body(&self.request_text(method, route, body).await?)
+    }
+
+    async fn request_text<B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        route: &str,
+        body: Option<&B>,
+    ) -> Result<String, PullRequestError> {
+        Ok(self.fetch(method, route, body).await?.0)
+    }
+
+    /// Sends a request and returns the body of the response with the route
+    /// of the next page, if the response is a page of a list.
+    async fn fetch<B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        route: &str,
+        body: Option<&B>,
+    ) -> Result<(String, Option<String>), PullRequestError> {
+        let body = body
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| PullRequestError::ApiError(e.to_string()))?;
+        // Merges and updates go through PUT and PATCH too, so only reads are
+        // treated as idempotent.
+        let idempotent = method == Method::GET;
+        let key = format!("{}{}", self.cache_scope, route);
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|_| idempotent && is_cacheable(route));
+        let cached = cache.and_then(|cache| cache.load(&key));
+
+        if self.cache_only {
+            return match cached {
+                Some(cached) => Ok((cached.body, cached.next)),
+                None => Err(PullRequestError::NotFound(format!(
+                    "{} is not cached",
+                    route
+                ))),
+            };
+        }
+
+        let etag = cached.as_ref().map(|cached| cached.etag.as_str());
+        let mut attempt = 0;
+        loop {
+            let delay = match self.send(&method, route, body.clone(), etag).await {
+                Ok((status, headers, text)) => {
+                    if let Some(rate_limit) = RateLimit::from_headers(&headers) {
+                        *self.rate_limit.lock().unwrap() = Some(rate_limit);
+                    }
+                    if let (StatusCode::NOT_MODIFIED, Some(cached)) = (status, &cached) {
+                        return Ok((cached.body.clone(), cached.next.clone()));
+                    }
+                    if status.is_success() {
+                        let next = next_page(&headers, &self.base_uri);
+                        let etag = headers.get(ETAG).and_then(|value| value.to_str().ok());
+                        if let (Some(cache), Some(etag)) = (cache, etag) {
+                            // A failed write only costs a full download next time.
+                            let _ = cache.store(&key, etag, &text, next.as_deref());
+                        }
+                        return Ok((text, next));
+                    }
+                    match self.retry.delay(status, &headers, idempotent, attempt) {
+                        Some(delay) => delay,
+                        None => return Err(error_from_response(status, &headers, &text)),
+                    }
+                }
+                Err(_) if idempotent && attempt < self.retry.max_retries => {
+                    self.retry.backoff(attempt)
+                }
+                Err(e) => return Err(e.into()),
+            };
+            attempt += 1;
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn send(
+        &self,
+        method: &Method,
+        route: &str,
+        body: Option<String>,
+        etag: Option<&str>,
+    ) -> Result<(StatusCode, HeaderMap, String), octocrab::Error> {
+        let mut builder = Request::builder()
+            .method(method.clone())
+            .uri(route)
+            .header(ACCEPT, "application/vnd.github+json");
+        if let Some(etag) = etag {
+            builder = builder.header(IF_NONE_MATCH, etag);
+        }
+        let request = match body {
+            Some(body) => builder.header(CONTENT_TYPE, "application/json").body(body),
+            None => builder.body(String::new()),
+        }
+        .expect("GitHub routes and headers are always valid");
+
+        let response = self.octocrab.execute(request).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let text = self.octocrab.body_to_string(response).await?;
+        Ok((status, headers, text))
+    }
+}
+
+/// The web address of the GitHub instance `api_url` belongs to, such as
+/// `https://github.com` for `https://api.github.com` and
+/// `https://ghe.example.com` for `https://ghe.example.com/api/v3`.
+pub fn web_url(api_url: &str) -> String {
+    let api_url = api_url.trim_end_matches('/');
+    match api_url.strip_suffix("/api/v3") {
+        Some(base) => base.to_string(),
+        None => api_url.replacen("://api.", "://", 1),
+    }
+}
+
+/// Percent-encodes a value for use in a query string.
+pub fn encode_query(value: &str) -> String {
+    encode(value, b"")
+}
+
+/// Percent-encodes a value for use in a path, such as a branch name in a
+/// compare route, keeping the slashes of names like `feature/x`.
+pub fn encode_path(value: &str) -> String {
+    encode(value, b"/")
+}
+
+fn encode(value: &str, keep: &[u8]) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ if keep.contains(&byte) => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// The route of the `rel="next"` entry of the `Link` header, relative to
+/// the API's base URI like every other route.
+fn next_page(headers: &HeaderMap, base_uri: &str) -> Option<String> {
+    let link = headers.get(LINK)?.to_str().ok()?;
+    let url = link.split(',').find_map(|entry| {
+        let (url, params) = entry.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>'))
+    })?;
+    match url.strip_prefix(base_uri) {
+        Some(route) => Some(route.to_string()),
+        // Another host, such as `api.github.com` behind a proxy: keep the
+        // path and the query.
+        None => {
+            let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+            Some(
+                path.find('/')
+                    .map_or("/", |index| &path[index..])
+                    .to_string(),
+            )
+        }
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(text: &str) -> Result<T, PullRequestError> {
+    let text = if text.trim().is_empty() { "null" } else { text };
+    serde_json::from_str(text)
+        .map_err(|e| PullRequestError::ApiError(format!("Unexpected response from GitHub: {}", e)))
+}
+
+fn error_from_response(status: StatusCode, headers: &HeaderMap, text: &str) -> PullRequestError {
+    let payload: Value = serde_json::from_str(text).unwrap_or(Value::Null);
+    let message = payload
+        .get("message")
+        .and_then(Value::as_str)
+        .or_else(|| status.canonical_reason())
+        .unwrap_or("Unknown error");
+    let errors = payload
+        .get("errors")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if is_rate_limited(status, headers) {
+        let reset = Local::now() + rate_limit_wait(headers);
+        return PullRequestError::RateLimited(format!(
+            "{} (resets at {})",
+            message.trim_end_matches('.'),
+            reset.format("%H:%M:%S")
+        ));
+    }
+    decode_github_error(status.as_u16(), message, errors)
+}
+
+/// How long until a rate limited request may be sent again: `Retry-After`,
+/// the reset of an exhausted limit, or GitHub's minimum wait for a secondary
+/// limit that says neither.
+fn rate_limit_wait(headers: &HeaderMap) -> Duration {
+    if let Some(seconds) = header_u64(headers, RETRY_AFTER.as_str()) {
+        return Duration::from_secs(seconds);
+    }
+    match RateLimit::from_headers(headers) {
+        Some(rate_limit) if rate_limit.remaining == 0 => {
+            Duration::from_secs(rate_limit.seconds_until_reset())
+        }
+        _ => SECONDARY_RATE_LIMIT_WAIT,
+    }
+}
+
+fn is_rate_limited(status: StatusCode, headers: &HeaderMap) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN
+            && (headers.contains_key(RETRY_AFTER)
+                || header_u64(headers, "x-ratelimit-remaining") == Some(0)))
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
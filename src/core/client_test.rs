@@ -0,0 +1,355 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::cache::ResponseCache;
+    use crate::core::client::{
+        encode_path, encode_query, web_url, GithubClient, RateLimit, RetryPolicy, SharedRateLimit,
+    };
+    use crate::core::errors::PullRequestError;
+    use http::header::{HeaderMap, HeaderValue};
+    use http::StatusCode;
+    use serde_json::{json, Value};
+    use std::env;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::process;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    type Requests = Arc<Mutex<Vec<String>>>;
+
+    /// Serves the canned responses in order, one per connection, and records
+    /// the head of each request it received.
+    fn stand_in_server(responses: Vec<String>) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Requests::default();
+        let received = requests.clone();
+
+        thread::spawn(move || {
+            for response in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let mut content_length = 0;
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    head.push_str(&line);
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap_or(0);
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                let _ = reader.read_exact(&mut body);
+                received.lock().unwrap().push(head.to_lowercase());
+                let _ = reader.get_mut().write_all(response.as_bytes());
+            }
+        });
+        (url, requests)
+    }
+
+    fn response(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+        let mut text = format!("HTTP/1.1 {}\r\n", status);
+        for (name, value) in headers {
+            text.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        text.push_str(&format!(
+            "Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        text
+    }
+
+    fn client(url: &str, rate_limit: SharedRateLimit) -> GithubClient {
+        GithubClient::new("token", url, rate_limit)
+            .unwrap()
+            .with_retry_policy(RetryPolicy {
+                max_retries: 3,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_secs(5),
+                max_rate_limit_wait: Duration::from_secs(2),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_get_retries_server_errors() {
+        let (url, requests) = stand_in_server(vec![
+            response("502 Bad Gateway", &[], r#"{"message":"Server Error"}"#),
+            response("502 Bad Gateway", &[], r#"{"message":"Server Error"}"#),
+            response(
+                "200 OK",
+                &[
+                    ("X-RateLimit-Limit", "5000"),
+                    ("X-RateLimit-Remaining", "4998"),
+                    ("X-RateLimit-Reset", "1700000000"),
+                ],
+                r#"{"ok":true}"#,
+            ),
+        ]);
+        let rate_limit = SharedRateLimit::default();
+
+        let value: Value = client(&url, rate_limit.clone()).get("/user").await.unwrap();
+
+        assert_eq!(value, json!({"ok": true}));
+        assert_eq!(requests.lock().unwrap().len(), 3);
+        assert_eq!(
+            *rate_limit.lock().unwrap(),
+            Some(RateLimit {
+                limit: 5000,
+                remaining: 4998,
+                reset: 1700000000,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_gives_up_after_max_retries() {
+        let (url, requests) = stand_in_server(vec![
+            response(
+                "502 Bad Gateway",
+                &[],
+                r#"{"message":"Server Error"}"#
+            );
+            4
+        ]);
+
+        let result: Result<Value, _> = client(&url, SharedRateLimit::default()).get("/user").await;
+
+        assert!(matches!(result, Err(PullRequestError::ApiError(_))));
+        assert_eq!(requests.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_create_is_not_retried_on_server_error() {
+        let (url, requests) = stand_in_server(vec![
+            response("502 Bad Gateway", &[], r#"{"message":"Server Error"}"#),
+            response("201 Created", &[], r#"{"number":1}"#),
+        ]);
+
+        let result: Result<Value, _> = client(&url, SharedRateLimit::default())
+            .post("/repos/djego/prt/pulls", &json!({"title": "Test"}))
+            .await;
+
+        assert!(matches!(result, Err(PullRequestError::ApiError(_))));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_secondary_rate_limit_is_reported_without_waiting() {
+        let (url, requests) = stand_in_server(vec![
+            response(
+                "429 Too Many Requests",
+                &[("Retry-After", "30")],
+                r#"{"message":"You have exceeded a secondary rate limit"}"#,
+            ),
+            response("201 Created", &[], r#"{"number":1}"#),
+        ]);
+        let started = Instant::now();
+
+        let result: Result<Value, _> = client(&url, SharedRateLimit::default())
+            .post("/repos/djego/prt/pulls", &json!({"title": "Test"}))
+            .await;
+
+        let Err(PullRequestError::RateLimited(message)) = result else {
+            panic!("expected a rate limit error, got {:?}", result);
+        };
+        assert!(message.contains("resets at"), "{}", message);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_short_rate_limit_wait_is_waited_out() {
+        let (url, requests) = stand_in_server(vec![
+            response(
+                "429 Too Many Requests",
+                &[("Retry-After", "1")],
+                r#"{"message":"You have exceeded a secondary rate limit"}"#,
+            ),
+            response("201 Created", &[], r#"{"number":1}"#),
+        ]);
+        let started = Instant::now();
+
+        let value: Value = client(&url, SharedRateLimit::default())
+            .post("/repos/djego/prt/pulls", &json!({"title": "Test"}))
+            .await
+            .unwrap();
+
+        assert_eq!(value, json!({"number": 1}));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_rate_limit_is_reported() {
+        let (url, requests) = stand_in_server(vec![response(
+            "403 Forbidden",
+            &[
+                ("X-RateLimit-Limit", "5000"),
+                ("X-RateLimit-Remaining", "0"),
+                ("X-RateLimit-Reset", "4102444800"),
+            ],
+            r#"{"message":"API rate limit exceeded for user ID 1."}"#,
+        )]);
+        let rate_limit = SharedRateLimit::default();
+
+        let result: Result<Value, _> = client(&url, rate_limit.clone()).get("/user").await;
+
+        assert!(matches!(result, Err(PullRequestError::RateLimited(_))));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert_eq!(rate_limit.lock().unwrap().unwrap().remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_is_not_retried() {
+        let (url, requests) = stand_in_server(vec![
+            response(
+                "403 Forbidden",
+                &[],
+                r#"{"message":"Resource not accessible"}"#,
+            ),
+            response("200 OK", &[], "{}"),
+        ]);
+
+        let result: Result<Value, _> = client(&url, SharedRateLimit::default()).get("/user").await;
+
+        assert!(matches!(result, Err(PullRequestError::Forbidden(_))));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_is_served_from_cache() {
+        let (url, requests) = stand_in_server(vec![
+            response("200 OK", &[("ETag", "\"v1\"")], r#"{"name":"prt"}"#),
+            response(
+                "304 Not Modified",
+                &[
+                    ("X-RateLimit-Limit", "5000"),
+                    ("X-RateLimit-Remaining", "4999"),
+                ],
+                "",
+            ),
+        ]);
+        let dir = env::temp_dir().join(format!("prt-client-cache-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let client = client(&url, SharedRateLimit::default()).with_cache(ResponseCache::new(dir));
+
+        let first: Value = client.get("/repos/djego/prt").await.unwrap();
+        let second: Value = client.get("/repos/djego/prt").await.unwrap();
+        let offline: Value = client
+            .clone()
+            .cache_only()
+            .get("/repos/djego/prt")
+            .await
+            .unwrap();
+
+        assert_eq!(first, json!({"name": "prt"}));
+        assert_eq!(second, first);
+        assert_eq!(offline, first);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+    }
+
+    #[tokio::test]
+    async fn test_get_all_follows_next_links() {
+        // The links may name another host, such as the one behind a proxy.
+        let next = concat!(
+            "<https://api.github.com/repos/djego/prt/pulls/1/reviews?page=2>; rel=\"next\", ",
+            "<https://api.github.com/repos/djego/prt/pulls/1/reviews?page=2>; rel=\"last\""
+        );
+        let (url, requests) = stand_in_server(vec![
+            response("200 OK", &[("Link", next)], "[1, 2]"),
+            response("200 OK", &[], "[3]"),
+        ]);
+
+        let items: Vec<u64> = client(&url, SharedRateLimit::default())
+            .get_all("/repos/djego/prt/pulls/1/reviews?per_page=2")
+            .await
+            .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3]);
+        let requests = requests.lock().unwrap();
+        assert!(requests[1].starts_with("get /repos/djego/prt/pulls/1/reviews?page=2 "));
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(3),
+            max_rate_limit_wait: Duration::from_secs(3),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_retry_policy_delay() {
+        let policy = RetryPolicy::default();
+        let empty = HeaderMap::new();
+        assert!(policy
+            .delay(StatusCode::BAD_GATEWAY, &empty, true, 0)
+            .is_some());
+        assert!(policy
+            .delay(StatusCode::BAD_GATEWAY, &empty, false, 0)
+            .is_none());
+        assert!(policy
+            .delay(StatusCode::BAD_GATEWAY, &empty, true, policy.max_retries)
+            .is_none());
+        assert!(policy
+            .delay(StatusCode::UNPROCESSABLE_ENTITY, &empty, true, 0)
+            .is_none());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("1"));
+        assert_eq!(
+            policy.delay(StatusCode::FORBIDDEN, &headers, false, 0),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.delay(StatusCode::TOO_MANY_REQUESTS, &headers, true, 0),
+            Some(Duration::from_secs(1))
+        );
+        headers.insert("retry-after", HeaderValue::from_static("3600"));
+        assert_eq!(
+            policy.delay(StatusCode::TOO_MANY_REQUESTS, &headers, true, 0),
+            None
+        );
+        // A secondary limit that doesn't say how long to wait.
+        assert_eq!(
+            policy.delay(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), true, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_web_url() {
+        assert_eq!(web_url("https://api.github.com"), "https://github.com");
+        assert_eq!(
+            web_url("https://ghe.example.com/api/v3/"),
+            "https://ghe.example.com"
+        );
+        assert_eq!(web_url("http://localhost:8080"), "http://localhost:8080");
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode_query("djego:feature/x"), "djego%3Afeature%2Fx");
+        assert_eq!(encode_query("main"), "main");
+        assert_eq!(encode_path("feature/#1 50%?"), "feature/%231%2050%25%3F");
+    }
+}
@@ -0,0 +1,14 @@
+use base64::prelude::{Engine, BASE64_STANDARD};
+use std::io::{self, Write};
+
+/// Copies `text` to the system clipboard with an OSC 52 escape sequence. The
+/// terminal emulator does the copying, so this also works over SSH.
+pub fn copy_to_clipboard(text: &str) -> Result<(), io::Error> {
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", osc52_sequence(text))?;
+    stdout.flush()
+}
+
+pub fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", BASE64_STANDARD.encode(text))
+}
@@ -0,0 +1,107 @@
+use octocrab::models::commits::{CommitComparison, GithubCommitStatus};
+
+#[derive(Clone)]
+pub struct FileChange {
+    pub filename: String,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// What a pull request from `source_branch` into `target_branch` would
+/// contain, fetched before the user confirms its creation.
+#[derive(Clone)]
+pub struct Comparison {
+    pub source_branch: String,
+    pub target_branch: String,
+    pub identical: bool,
+    pub total_commits: u64,
+    pub behind_by: u64,
+    pub files: Vec<FileChange>,
+    pub existing_pull_request: Option<u64>,
+}
+
+impl Comparison {
+    pub fn new(
+        source_branch: String,
+        target_branch: String,
+        comparison: &CommitComparison,
+        existing_pull_request: Option<u64>,
+    ) -> Comparison {
+        let files = comparison
+            .files
+            .iter()
+            .flatten()
+            .map(|file| FileChange {
+                filename: file.filename.clone(),
+                additions: file.additions,
+                deletions: file.deletions,
+            })
+            .collect();
+
+        Comparison {
+            source_branch,
+            target_branch,
+            identical: matches!(comparison.status, GithubCommitStatus::Identical),
+            total_commits: comparison.total_commits.max(0) as u64,
+            behind_by: comparison.behind_by.max(0) as u64,
+            files,
+            existing_pull_request,
+        }
+    }
+
+    pub fn additions(&self) -> u64 {
+        self.files.iter().map(|file| file.additions).sum()
+    }
+
+    pub fn deletions(&self) -> u64 {
+        self.files.iter().map(|file| file.deletions).sum()
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.identical || self.total_commits == 0 {
+            warnings.push(format!(
+                "There are no commits between {} and {}",
+                self.target_branch, self.source_branch
+            ));
+        }
+        if self.behind_by > 0 {
+            warnings.push(format!(
+                "{} is {} commit(s) behind {}",
+                self.source_branch, self.behind_by, self.target_branch
+            ));
+        }
+        if let Some(number) = self.existing_pull_request {
+            warnings.push(format!(
+                "Pull request #{} already exists for {}",
+                number, self.source_branch
+            ));
+        }
+        warnings
+    }
+}
+
+/// Splits `width` columns between additions and deletions like `git diff
+/// --stat`, scaled against the largest change in the comparison.
+pub fn diffstat_bar(
+    additions: u64,
+    deletions: u64,
+    max_changes: u64,
+    width: u64,
+) -> (usize, usize) {
+    let changes = additions + deletions;
+    if changes == 0 || max_changes == 0 {
+        return (0, 0);
+    }
+    let scaled = if max_changes <= width {
+        changes
+    } else {
+        (changes * width).div_ceil(max_changes).max(1)
+    };
+    let plus = if additions == 0 {
+        0
+    } else {
+        ((additions * scaled) / changes).max(1)
+    };
+    (plus as usize, (scaled - plus.min(scaled)) as usize)
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::compare::{diffstat_bar, Comparison, FileChange};
+
+    fn comparison() -> Comparison {
+        Comparison {
+            source_branch: "feature".to_string(),
+            target_branch: "main".to_string(),
+            identical: false,
+            total_commits: 2,
+            behind_by: 0,
+            files: vec![
+                FileChange {
+                    filename: "src/main.rs".to_string(),
+                    additions: 10,
+                    deletions: 2,
+                },
+                FileChange {
+                    filename: "README.md".to_string(),
+                    additions: 1,
+                    deletions: 0,
+                },
+            ],
+            existing_pull_request: None,
+        }
+    }
+
+    #[test]
+    fn test_totals() {
+        let comparison = comparison();
+        assert_eq!(comparison.additions(), 11);
+        assert_eq!(comparison.deletions(), 2);
+        assert!(comparison.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_warnings() {
+        let mut comparison = comparison();
+        comparison.identical = true;
+        comparison.total_commits = 0;
+        comparison.behind_by = 3;
+        comparison.existing_pull_request = Some(7);
+
+        assert_eq!(
+            comparison.warnings(),
+            vec![
+                "There are no commits between main and feature".to_string(),
+                "feature is 3 commit(s) behind main".to_string(),
+                "Pull request #7 already exists for feature".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diffstat_bar() {
+        assert_eq!(diffstat_bar(0, 0, 0, 20), (0, 0));
+        assert_eq!(diffstat_bar(3, 2, 10, 20), (3, 2));
+        assert_eq!(diffstat_bar(100, 100, 200, 20), (10, 10));
+        assert_eq!(diffstat_bar(1, 0, 1000, 20), (1, 0));
+        assert_eq!(diffstat_bar(0, 1, 1000, 20), (0, 1));
+    }
+}
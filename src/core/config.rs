@@ -1,3 +1,5 @@
+use crate::core::keymap::KeymapConfig;
+use crate::core::theme::ThemeConfig;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs::{self, File};
@@ -6,7 +8,15 @@ use std::path::PathBuf;
 
 #[derive(Deserialize, Serialize)]
 pub struct Config {
+    /// Whether prt captures the mouse. Turn it off to keep the terminal's own
+    /// text selection.
+    #[serde(default = "enabled", skip_serializing_if = "is_enabled")]
+    pub mouse: bool,
     pub github: GitHubConfig,
+    #[serde(default, skip_serializing_if = "KeymapConfig::is_empty")]
+    pub keymap: KeymapConfig,
+    #[serde(default, skip_serializing_if = "ThemeConfig::is_empty")]
+    pub theme: ThemeConfig,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -14,37 +24,69 @@ pub struct GitHubConfig {
     pub pat: String,
 }
 
-pub fn load_config() -> Option<Config> {
-    let home_dir = env::var("HOME").expect("No se pudo obtener el directorio home");
-    let mut config_path = PathBuf::from(home_dir);
-    config_path.push(".prt");
-    config_path.push("config.toml");
+fn enabled() -> bool {
+    true
+}
+
+fn is_enabled(value: &bool) -> bool {
+    *value
+}
 
-    if let Ok(config_content) = fs::read_to_string(config_path) {
-        let config: Config =
-            toml::from_str(&config_content).expect("Failed to parse configuration file");
-        Some(config)
-    } else {
-        None
+/// `~/.prt`, or `.prt` in the working directory when `HOME` isn't set.
+pub fn config_dir() -> PathBuf {
+    env::var_os("HOME")
+        .map_or_else(PathBuf::new, PathBuf::from)
+        .join(".prt")
+}
+
+pub fn config_path() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+/// The config file, or `None` when there is none yet. A file that can't
+/// be read or parsed is an error, shown as a warning at startup.
+pub fn load_config() -> Result<Option<Config>, String> {
+    let path = config_path();
+    match fs::read_to_string(&path) {
+        Ok(content) => parse_config(&content)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
     }
 }
 
-pub fn save_config(pat: &str) -> Result<(), io::Error> {
+pub fn parse_config(content: &str) -> Result<Config, String> {
+    toml::from_str(content).map_err(|e: toml::de::Error| match e.span() {
+        Some(span) => {
+            let line = content[..span.start].matches('\n').count() + 1;
+            format!("line {}: {}", line, e.message())
+        }
+        None => e.message().to_string(),
+    })
+}
+
+pub fn save_config(pat: &str) -> Result<(), String> {
+    // Keep the rest of the file, such as the keymap, when the token changes.
+    // A file that doesn't parse is left alone rather than overwritten.
+    let (mouse, keymap, theme) = load_config()?
+        .map(|config| (config.mouse, config.keymap, config.theme))
+        .unwrap_or((true, KeymapConfig::default(), ThemeConfig::default()));
     let config = Config {
+        mouse,
         github: GitHubConfig {
             pat: pat.to_string(),
         },
+        keymap,
+        theme,
     };
 
-    let toml_str = toml::to_string(&config).expect("Failed to serialize configuration");
-    let home_dir = env::var("HOME").expect("No se pudo obtener el directorio home");
-    let mut config_path = PathBuf::from(home_dir);
-    config_path.push(".prt");
-    fs::create_dir_all(&config_path)?;
-    config_path.push("config.toml");
-
-    let mut file = File::create(config_path)?;
-    file.write_all(toml_str.as_bytes())?;
+    let toml_str = toml::to_string(&config).map_err(|e| e.to_string())?;
+    write_config(&toml_str).map_err(|e| format!("{}: {}", config_path().display(), e))
+}
 
-    Ok(())
+fn write_config(content: &str) -> io::Result<()> {
+    fs::create_dir_all(config_dir())?;
+    let mut file = File::create(config_path())?;
+    file.write_all(content.as_bytes())
 }
@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::config::parse_config;
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config("mouse = false\n[github]\npat = \"token\"\n").unwrap();
+        assert!(!config.mouse);
+        assert_eq!(config.github.pat, "token");
+        assert!(config.keymap.is_empty());
+    }
+
+    #[test]
+    fn test_parse_config_reports_the_line_of_an_error() {
+        let Err(error) = parse_config("[github]\npat = \"token\"\n[keymap]\npreset = 3\n") else {
+            panic!("a number is not a preset");
+        };
+        assert!(error.starts_with("line 4: "), "{}", error);
+        assert!(parse_config("[github\n").is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use std::backtrace::Backtrace;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// The panic hook must never panic itself, so unlike `config_dir` this falls
+// back to the temp directory when HOME is not set.
+pub fn crash_log_path() -> PathBuf {
+    let mut path = env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".prt"))
+        .unwrap_or_else(env::temp_dir);
+    path.push("crash.log");
+    path
+}
+
+pub fn write_crash_report(info: &PanicHookInfo) -> Result<PathBuf, io::Error> {
+    let path = crash_log_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(
+        file,
+        "=== prt {} crash at {} ===",
+        env!("CARGO_PKG_VERSION"),
+        timestamp
+    )?;
+    writeln!(file, "{}", info)?;
+    writeln!(file, "{}", Backtrace::force_capture())?;
+
+    Ok(path)
+}
@@ -0,0 +1,181 @@
+use crate::core::pull_request::{ChecksState, PullRequestStatus, ReviewDecision};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// How often the dashboard refreshes while it is open.
+pub const DASHBOARD_REFRESH: Duration = Duration::from_secs(300);
+/// Pull requests fetched per search, which also bounds the status lookups
+/// done for each of them.
+pub const DASHBOARD_SEARCH_LIMIT: u8 = 20;
+/// Teams searched for review requests, to stay within the search API limit.
+pub const DASHBOARD_MAX_TEAMS: usize = 5;
+/// Status lookups running at once, to stay clear of the secondary rate
+/// limit.
+pub const DASHBOARD_LOOKUPS: usize = 6;
+
+/// Why a pull request shows up on the dashboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Involvement {
+    Authored,
+    ReviewRequested,
+    Assigned,
+    Team(String),
+}
+
+impl Involvement {
+    pub fn query(&self) -> String {
+        let qualifier = match self {
+            Involvement::Authored => "author:@me".to_string(),
+            Involvement::ReviewRequested => "review-requested:@me".to_string(),
+            Involvement::Assigned => "assignee:@me".to_string(),
+            Involvement::Team(team) => format!("team-review-requested:{}", team),
+        };
+        format!("is:pr is:open archived:false {}", qualifier)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Involvement::Authored => "author".to_string(),
+            Involvement::ReviewRequested => "review requested".to_string(),
+            Involvement::Assigned => "assigned".to_string(),
+            Involvement::Team(team) => format!("@{}", team),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchResults {
+    pub items: Vec<SearchItem>,
+}
+
+/// The fields of a search result the dashboard needs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchItem {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    pub repository_url: String,
+    pub user: Option<SearchUser>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub draft: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchUser {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserTeam {
+    pub slug: String,
+    pub organization: TeamOrganization,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamOrganization {
+    pub login: String,
+}
+
+/// The pull requests of the searches that worked, and why the others
+/// failed.
+#[derive(Debug)]
+pub struct Dashboard {
+    pub entries: Vec<DashboardEntry>,
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DashboardEntry {
+    pub repository: String,
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub url: String,
+    pub draft: bool,
+    pub created_at: DateTime<Utc>,
+    pub involvement: Vec<Involvement>,
+    pub checks: ChecksState,
+    pub review: ReviewDecision,
+    /// Why the checks and review couldn't be looked up, when they couldn't.
+    pub status_error: Option<String>,
+}
+
+impl DashboardEntry {
+    pub fn from_search_item(item: &SearchItem) -> Option<DashboardEntry> {
+        Some(DashboardEntry {
+            repository: repository_from_url(&item.repository_url)?,
+            number: item.number,
+            title: item.title.clone(),
+            author: item
+                .user
+                .as_ref()
+                .map(|user| user.login.clone())
+                .unwrap_or_default(),
+            url: item.html_url.clone(),
+            draft: item.draft,
+            created_at: item.created_at,
+            involvement: Vec::new(),
+            checks: ChecksState::None,
+            review: ReviewDecision::None,
+            status_error: None,
+        })
+    }
+
+    /// Takes the checks and review of a status lookup, or keeps the entry
+    /// with its status unknown when the lookup failed.
+    pub fn set_status(&mut self, status: Result<PullRequestStatus, String>) {
+        match status {
+            Ok(status) => {
+                self.checks = status.checks;
+                self.review = status.review;
+                self.status_error = None;
+            }
+            Err(e) => self.status_error = Some(e),
+        }
+    }
+}
+
+/// Adds the results of the search for `involvement`, merging pull requests
+/// found by earlier searches.
+pub fn merge_results(
+    entries: &mut Vec<DashboardEntry>,
+    involvement: Involvement,
+    items: &[SearchItem],
+) {
+    for item in items {
+        let Some(mut entry) = DashboardEntry::from_search_item(item) else {
+            continue;
+        };
+        match entries
+            .iter_mut()
+            .find(|e| e.repository == entry.repository && e.number == entry.number)
+        {
+            Some(existing) => {
+                if !existing.involvement.contains(&involvement) {
+                    existing.involvement.push(involvement.clone());
+                }
+            }
+            None => {
+                entry.involvement.push(involvement.clone());
+                entries.push(entry);
+            }
+        }
+    }
+}
+
+/// Groups entries by repository, newest pull request first in each group.
+pub fn sort_dashboard(entries: &mut [DashboardEntry]) {
+    entries.sort_by(|a, b| {
+        a.repository
+            .cmp(&b.repository)
+            .then_with(|| b.created_at.cmp(&a.created_at))
+    });
+}
+
+/// Turns `https://api.github.com/repos/{owner}/{repo}` into `owner/repo`.
+pub fn repository_from_url(url: &str) -> Option<String> {
+    let (_, full_name) = url.trim_end_matches('/').split_once("/repos/")?;
+    full_name.contains('/').then(|| full_name.to_string())
+}
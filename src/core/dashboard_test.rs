@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::dashboard::{
+        merge_results, repository_from_url, sort_dashboard, DashboardEntry, Involvement, SearchItem,
+    };
+    use crate::core::pull_request::{ChecksState, PullRequestStatus, ReviewDecision};
+    use serde_json::json;
+
+    fn item(repository: &str, number: u64, created_at: &str) -> SearchItem {
+        serde_json::from_value(json!({
+            "number": number,
+            "title": format!("PR {}", number),
+            "html_url": format!("https://github.com/{}/pull/{}", repository, number),
+            "repository_url": format!("https://api.github.com/repos/{}", repository),
+            "user": { "login": "djego" },
+            "created_at": created_at
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_queries() {
+        assert_eq!(
+            Involvement::Authored.query(),
+            "is:pr is:open archived:false author:@me"
+        );
+        assert_eq!(
+            Involvement::Team("acme/core".to_string()).query(),
+            "is:pr is:open archived:false team-review-requested:acme/core"
+        );
+    }
+
+    #[test]
+    fn test_merge_and_sort() {
+        let mut entries = Vec::new();
+        merge_results(
+            &mut entries,
+            Involvement::Authored,
+            &[
+                item("djego/prt", 1, "2024-05-01T10:00:00Z"),
+                item("acme/api", 7, "2024-05-01T10:00:00Z"),
+            ],
+        );
+        merge_results(
+            &mut entries,
+            Involvement::Assigned,
+            &[
+                item("djego/prt", 1, "2024-05-01T10:00:00Z"),
+                item("djego/prt", 2, "2024-05-03T10:00:00Z"),
+            ],
+        );
+
+        sort_dashboard(&mut entries);
+
+        let keys: Vec<(&str, u64)> = entries
+            .iter()
+            .map(|entry| (entry.repository.as_str(), entry.number))
+            .collect();
+        assert_eq!(keys, [("acme/api", 7), ("djego/prt", 2), ("djego/prt", 1)]);
+        assert_eq!(
+            entries[2].involvement,
+            [Involvement::Authored, Involvement::Assigned]
+        );
+        assert_eq!(entries[2].author, "djego");
+    }
+
+    #[test]
+    fn test_failed_status_lookup_keeps_the_entry() {
+        let mut entry =
+            DashboardEntry::from_search_item(&item("djego/prt", 1, "2024-05-01T10:00:00Z"))
+                .unwrap();
+        entry.set_status(Err("Not Found".to_string()));
+        assert_eq!(entry.status_error.as_deref(), Some("Not Found"));
+        assert_eq!(entry.checks, ChecksState::None);
+
+        entry.set_status(Ok(PullRequestStatus {
+            checks: ChecksState::Success,
+            review: ReviewDecision::Approved,
+            runs: Vec::new(),
+        }));
+        assert_eq!(entry.status_error, None);
+        assert_eq!(entry.review, ReviewDecision::Approved);
+    }
+
+    #[test]
+    fn test_repository_from_url() {
+        assert_eq!(
+            repository_from_url("https://api.github.com/repos/djego/prt").as_deref(),
+            Some("djego/prt")
+        );
+        assert_eq!(
+            repository_from_url("https://ghe.example.com/api/v3/repos/acme/api/").as_deref(),
+            Some("acme/api")
+        );
+        assert_eq!(
+            repository_from_url("https://api.github.com/users/djego"),
+            None
+        );
+    }
+}
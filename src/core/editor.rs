@@ -0,0 +1,158 @@
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many names are tried for the temporary file before giving up.
+const TEMP_FILE_ATTEMPTS: u32 = 100;
+
+/// The editor to run, from `$VISUAL`, then `$EDITOR`, falling back to `vi`
+/// (`notepad` on Windows). The variables may include arguments, quoted as in
+/// a shell.
+pub fn editor_command() -> Result<Vec<String>, String> {
+    choose_editor(env::var("VISUAL").ok(), env::var("EDITOR").ok())
+}
+
+pub fn choose_editor(
+    visual: Option<String>,
+    editor: Option<String>,
+) -> Result<Vec<String>, String> {
+    let command = [visual, editor]
+        .into_iter()
+        .flatten()
+        .find(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+    split_command(&command)
+        .ok_or_else(|| format!("Unmatched quote in the editor command {}", command))
+}
+
+/// Splits `command` into words the way a shell does: whitespace separates
+/// words, single quotes keep everything literally, double quotes keep
+/// everything but `\"`, `\\`, `\$` and `` \` `` escapes, and a backslash
+/// outside quotes escapes the next character. `None` when a quote is left
+/// open.
+pub fn split_command(command: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            '\n' => {}
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                let word = word.get_or_insert_with(String::new);
+                match chars.next() {
+                    Some('\n') => {}
+                    Some(c) => word.push(c),
+                    None => word.push('\\'),
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Some(words)
+}
+
+/// Creates a new temporary file ending in `file_name` that only the user
+/// can read, trying another name when one is taken so an existing file or
+/// link is never followed.
+fn create_temp_file(file_name: &str) -> Result<(PathBuf, File), io::Error> {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.subsec_nanos());
+    for attempt in 0..TEMP_FILE_ATTEMPTS {
+        let path = env::temp_dir().join(format!(
+            "prt-{}-{:08x}-{}",
+            std::process::id(),
+            seed.wrapping_add(attempt),
+            file_name
+        ));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "could not find a free name for the temporary file",
+    ))
+}
+
+/// Writes `text` to a temporary file named after `file_name`, waits for
+/// `command` to edit it and returns the new contents. The trailing newline
+/// editors add is dropped. The terminal must be handed over to the editor
+/// before calling this.
+pub fn edit_text(command: &[String], text: &str, file_name: &str) -> Result<String, io::Error> {
+    let Some((program, args)) = command.split_first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "No editor configured, set $VISUAL or $EDITOR",
+        ));
+    };
+    let (path, mut file) = create_temp_file(file_name)?;
+    if let Err(e) = file.write_all(text.as_bytes()) {
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+    drop(file);
+
+    let status = Command::new(program).args(args).arg(&path).status();
+    let edited = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    let status = status?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "{} exited with {}, keeping the previous text",
+            program, status
+        )));
+    }
+    let edited = edited?;
+    Ok(edited
+        .strip_suffix('\n')
+        .map(|text| text.strip_suffix('\r').unwrap_or(text))
+        .unwrap_or(&edited)
+        .to_string())
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::editor::{choose_editor, edit_text, split_command};
+
+    fn command(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|part| part.to_string()).collect()
+    }
+
+    #[test]
+    fn test_choose_editor() {
+        assert_eq!(
+            choose_editor(Some("code --wait".to_string()), Some("vim".to_string())).unwrap(),
+            ["code", "--wait"]
+        );
+        assert_eq!(
+            choose_editor(Some(" ".to_string()), Some("vim".to_string())).unwrap(),
+            ["vim"]
+        );
+        assert_eq!(choose_editor(None, None).unwrap().len(), 1);
+        assert!(choose_editor(Some("\"/opt/My Editor".to_string()), None).is_err());
+    }
+
+    #[test]
+    fn test_split_command() {
+        let split = |command| split_command(command).unwrap();
+        assert_eq!(
+            split("\"/Applications/Sublime Text.app/subl\" -w"),
+            ["/Applications/Sublime Text.app/subl", "-w"]
+        );
+        assert_eq!(split("vim -c 'set tw=72'"), ["vim", "-c", "set tw=72"]);
+        assert_eq!(split("/opt/My\\ Editor/edit"), ["/opt/My Editor/edit"]);
+        assert_eq!(
+            split("emacs \"\\\"q\\\"\" '\\n' ''"),
+            ["emacs", "\"q\"", "\\n", ""]
+        );
+        assert_eq!(split("  nano  "), ["nano"]);
+        assert_eq!(split_command("vim 'open"), None);
+        assert_eq!(split_command("vim \"open"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_text() {
+        let edited = edit_text(
+            &command(&["sh", "-c", "printf 'more\\n' >> \"$0\""]),
+            "## Summary\n",
+            "test_edit_text.md",
+        )
+        .unwrap();
+        assert_eq!(edited, "## Summary\nmore");
+
+        let failed = edit_text(&command(&["sh", "-c", "exit 1"]), "text", "test_failed.md");
+        assert!(failed.is_err());
+        assert!(edit_text(&[], "text", "test_empty.md").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_text_uses_a_private_file() {
+        let mode = edit_text(
+            &command(&["sh", "-c", "ls -l \"$0\" | cut -c1-10 > \"$0\""]),
+            "",
+            "test_private.md",
+        )
+        .unwrap();
+        assert_eq!(mode, "-rw-------");
+    }
+}
@@ -1,16 +1,182 @@
+use serde_json::Value;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum PullRequestError {
     #[error("GitHub API error: {0}")]
-    ApiError(#[from] octocrab::Error),
+    ApiError(String),
 
-    #[error("Validation failed")]
+    #[error("Validation failed: {0}")]
     PullRequestValidationFailed(String),
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     #[error("Repo Not found: {0}")]
     RepoNotFound(String),
+
+    #[error("Pull request #{0} not found")]
+    PullRequestNotFound(u64),
+
+    #[error("Pull request is not mergeable: {0}")]
+    PullRequestNotMergeable(String),
+
+    #[error("GitHub refused the request: {0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    NoCommitsBetween(String),
+
+    #[error("{0}")]
+    PullRequestAlreadyExists(String),
+
+    #[error("The {0} branch is not valid")]
+    InvalidBranch(String),
+
+    #[error("GitHub rate limit exceeded: {0}")]
+    RateLimited(String),
+
+    #[error("Organization SSO authorization required: {0}")]
+    SsoRequired(String),
+
+    #[error("GitHub rejected the token: {0}")]
+    BadCredentials(String),
+
+    #[error("Permission denied: {0}")]
+    Forbidden(String),
+
+    #[error("Could not reach GitHub: {0}")]
+    Network(String),
+}
+
+impl PullRequestError {
+    /// What the user can do about the error, shown under it in the Output
+    /// panel. `sync_key` is the label of the key that syncs, such as `[s]`,
+    /// and `web_url` the web address of the GitHub instance in use.
+    pub fn suggestion(&self, sync_key: Option<&str>, web_url: &str) -> Option<String> {
+        let suggestion = match self {
+            PullRequestError::NoCommitsBetween(_) => {
+                Some("Push your commits to the source branch, or pick another target branch")
+            }
+            PullRequestError::PullRequestAlreadyExists(_) => {
+                return Some(match sync_key {
+                    Some(key) => {
+                        format!("Press {} to sync and open the existing pull request", key)
+                    }
+                    None => "Sync to open the existing pull request".to_string(),
+                });
+            }
+            PullRequestError::InvalidBranch(_) => {
+                Some("Check that the branch exists on GitHub and has been pushed")
+            }
+            PullRequestError::RateLimited(_) => Some("Wait a few minutes before trying again"),
+            PullRequestError::SsoRequired(_) => {
+                return Some(format!(
+                    "Authorize the token for the organization at {}/settings/tokens",
+                    web_url
+                ));
+            }
+            PullRequestError::BadCredentials(_) => {
+                return Some(format!(
+                    "The token may be expired or revoked, create a new one at {}/settings/tokens",
+                    web_url
+                ));
+            }
+            PullRequestError::Forbidden(_) => {
+                Some("Check that the token has the repo scope and access to this repository")
+            }
+            PullRequestError::RepoNotFound(_) => {
+                Some("Check the repository name and that the token can access it")
+            }
+            PullRequestError::Network(_) => Some("Check your network connection and try again"),
+            _ => None,
+        };
+        suggestion.map(str::to_string)
+    }
+
+    pub fn message_with_hint(&self, sync_key: Option<&str>, web_url: &str) -> String {
+        match self.suggestion(sync_key, web_url) {
+            Some(suggestion) => format!("{}\n Hint: {}", self, suggestion),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl From<octocrab::Error> for PullRequestError {
+    fn from(error: octocrab::Error) -> PullRequestError {
+        match error {
+            octocrab::Error::GitHub { source, .. } => decode_github_error(
+                source.status_code.as_u16(),
+                &source.message,
+                source.errors.as_deref().unwrap_or_default(),
+            ),
+            octocrab::Error::Hyper { source, .. } => PullRequestError::Network(source.to_string()),
+            octocrab::Error::Service { source, .. } => {
+                PullRequestError::Network(source.to_string())
+            }
+            e => PullRequestError::ApiError(e.to_string()),
+        }
+    }
+}
+
+/// Turns GitHub's error payload (`message` plus `errors[]` entries with
+/// `resource`, `field`, `code` and `message`) into a typed error.
+pub fn decode_github_error(status: u16, message: &str, errors: &[Value]) -> PullRequestError {
+    let details: Vec<String> = errors.iter().map(describe_error_entry).collect();
+    let lower = message.to_lowercase();
+
+    match status {
+        401 => PullRequestError::BadCredentials(message.to_string()),
+        404 => PullRequestError::NotFound(message.to_string()),
+        403 | 429 if lower.contains("rate limit") || status == 429 => {
+            PullRequestError::RateLimited(message.to_string())
+        }
+        403 if lower.contains("saml") || lower.contains("sso") => {
+            PullRequestError::SsoRequired(message.to_string())
+        }
+        403 => PullRequestError::Forbidden(message.to_string()),
+        405 | 409 => PullRequestError::Conflict(message.to_string()),
+        422 => {
+            for entry in errors {
+                let field = entry.get("field").and_then(Value::as_str);
+                let code = entry.get("code").and_then(Value::as_str);
+                let text = entry.get("message").and_then(Value::as_str).unwrap_or("");
+                if text.starts_with("No commits between") {
+                    return PullRequestError::NoCommitsBetween(text.to_string());
+                }
+                if text.starts_with("A pull request already exists") {
+                    return PullRequestError::PullRequestAlreadyExists(text.to_string());
+                }
+                if let (Some(field @ ("base" | "head")), Some("invalid")) = (field, code) {
+                    return PullRequestError::InvalidBranch(field.to_string());
+                }
+            }
+            if details.is_empty() {
+                PullRequestError::PullRequestValidationFailed(message.to_string())
+            } else {
+                PullRequestError::PullRequestValidationFailed(details.join(", "))
+            }
+        }
+        _ if details.is_empty() => PullRequestError::ApiError(message.to_string()),
+        _ => PullRequestError::ApiError(format!("{} ({})", message, details.join(", "))),
+    }
+}
+
+fn describe_error_entry(entry: &Value) -> String {
+    if let Some(text) = entry.as_str() {
+        return text.to_string();
+    }
+    if let Some(text) = entry.get("message").and_then(Value::as_str) {
+        return text.to_string();
+    }
+    let part = |key: &str| entry.get(key).and_then(Value::as_str).unwrap_or("?");
+    match part("code") {
+        "missing_field" => format!("{} is missing", part("field")),
+        "invalid" => format!("{} is invalid", part("field")),
+        "already_exists" => format!("{} already exists", part("resource")),
+        code => format!("{} {} {}", part("resource"), part("field"), code),
+    }
 }
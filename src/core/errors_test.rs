@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::errors::{decode_github_error, PullRequestError};
+    use serde_json::json;
+
+    const GITHUB: &str = "https://github.com";
+
+    #[test]
+    fn test_decode_no_commits_between() {
+        let error = decode_github_error(
+            422,
+            "Validation Failed",
+            &[json!({
+                "resource": "PullRequest",
+                "code": "custom",
+                "message": "No commits between main and feature"
+            })],
+        );
+        assert!(matches!(error, PullRequestError::NoCommitsBetween(_)));
+        assert_eq!(error.to_string(), "No commits between main and feature");
+        assert!(error.suggestion(None, GITHUB).is_some());
+    }
+
+    #[test]
+    fn test_decode_pull_request_already_exists() {
+        let error = decode_github_error(
+            422,
+            "Validation Failed",
+            &[json!({
+                "resource": "PullRequest",
+                "code": "custom",
+                "message": "A pull request already exists for djego:feature."
+            })],
+        );
+        assert!(matches!(
+            error,
+            PullRequestError::PullRequestAlreadyExists(_)
+        ));
+        assert_eq!(
+            error.suggestion(Some("[g]"), GITHUB).as_deref(),
+            Some("Press [g] to sync and open the existing pull request")
+        );
+        assert_eq!(
+            error.suggestion(None, GITHUB).as_deref(),
+            Some("Sync to open the existing pull request")
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_base() {
+        let error = decode_github_error(
+            422,
+            "Validation Failed",
+            &[json!({ "resource": "PullRequest", "field": "base", "code": "invalid" })],
+        );
+        assert!(matches!(error, PullRequestError::InvalidBranch(ref field) if field == "base"));
+        assert_eq!(error.to_string(), "The base branch is not valid");
+    }
+
+    #[test]
+    fn test_decode_keeps_validation_details() {
+        let error = decode_github_error(
+            422,
+            "Validation Failed",
+            &[json!({ "resource": "PullRequest", "field": "title", "code": "missing_field" })],
+        );
+        assert_eq!(error.to_string(), "Validation failed: title is missing");
+    }
+
+    #[test]
+    fn test_decode_authentication_errors() {
+        assert!(matches!(
+            decode_github_error(401, "Bad credentials", &[]),
+            PullRequestError::BadCredentials(_)
+        ));
+        assert!(matches!(
+            decode_github_error(403, "API rate limit exceeded for user ID 1.", &[]),
+            PullRequestError::RateLimited(_)
+        ));
+        assert!(matches!(
+            decode_github_error(429, "Too many requests", &[]),
+            PullRequestError::RateLimited(_)
+        ));
+        assert!(matches!(
+            decode_github_error(
+                403,
+                "Resource protected by organization SAML enforcement.",
+                &[]
+            ),
+            PullRequestError::SsoRequired(_)
+        ));
+        assert!(matches!(
+            decode_github_error(403, "Must have admin rights to Repository.", &[]),
+            PullRequestError::Forbidden(_)
+        ));
+    }
+
+    #[test]
+    fn test_token_hints_link_to_the_web_host() {
+        let error = decode_github_error(401, "Bad credentials", &[]);
+        assert_eq!(
+            error.suggestion(None, "https://ghe.example.com").as_deref(),
+            Some("The token may be expired or revoked, create a new one at https://ghe.example.com/settings/tokens")
+        );
+    }
+
+    #[test]
+    fn test_message_with_hint() {
+        let error = decode_github_error(401, "Bad credentials", &[]);
+        assert!(error
+            .message_with_hint(None, GITHUB)
+            .starts_with("GitHub rejected the token: Bad credentials\n Hint: "));
+        assert_eq!(
+            PullRequestError::InvalidInput("Title is empty".to_string())
+                .message_with_hint(None, GITHUB),
+            "Invalid input: Title is empty"
+        );
+    }
+
+    #[test]
+    fn test_decode_not_found_and_conflict() {
+        assert!(matches!(
+            decode_github_error(404, "Not Found", &[]),
+            PullRequestError::NotFound(_)
+        ));
+        assert!(matches!(
+            decode_github_error(409, "Reference update failed", &[]),
+            PullRequestError::Conflict(_)
+        ));
+    }
+}
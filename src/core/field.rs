@@ -0,0 +1,56 @@
+/// The fields of the pull request form, in focus order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Title,
+    Description,
+    SourceBranch,
+    TargetBranch,
+}
+
+impl Field {
+    pub const ALL: [Field; 4] = [
+        Field::Title,
+        Field::Description,
+        Field::SourceBranch,
+        Field::TargetBranch,
+    ];
+
+    pub fn from_index(index: usize) -> Field {
+        Field::ALL[index % Field::ALL.len()]
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Field::Title => "Title",
+            Field::Description => "Description",
+            Field::SourceBranch => "Source Branch",
+            Field::TargetBranch => "Target Branch",
+        }
+    }
+
+    /// What the field is for, in the help overlay.
+    pub fn help(self) -> &'static str {
+        match self {
+            Field::Title => "A one-line summary of the change",
+            Field::Description => "The body of the pull request, in Markdown",
+            Field::SourceBranch => "The branch with the changes",
+            Field::TargetBranch => "The branch to merge the changes into",
+        }
+    }
+
+    pub fn is_multiline(self) -> bool {
+        self == Field::Description
+    }
+
+    pub fn next(self) -> Field {
+        Field::from_index(self.index() + 1)
+    }
+
+    pub fn previous(self) -> Field {
+        Field::from_index(self.index() + Field::ALL.len() - 1)
+    }
+}
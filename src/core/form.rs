@@ -0,0 +1,120 @@
+use crate::core::errors::PullRequestError;
+use crate::core::field::Field;
+use crate::core::pull_request::PullRequest;
+use crate::core::text_input::TextInput;
+use crossterm::event::KeyEvent;
+
+/// The create and edit form. It owns one editor per field and is the only
+/// place the pull request being written is kept; `validate` turns it into
+/// the `PullRequest` sent to GitHub.
+#[derive(Debug, Clone)]
+pub struct PullRequestForm {
+    inputs: Vec<TextInput>,
+    /// Which fields the user typed or pasted into, by field index.
+    edited: Vec<bool>,
+    pub draft: bool,
+}
+
+impl PullRequestForm {
+    pub fn new(source_branch: &str, target_branch: &str) -> PullRequestForm {
+        let mut form = PullRequestForm {
+            inputs: Field::ALL
+                .iter()
+                .map(|field| {
+                    if field.is_multiline() {
+                        TextInput::multiline()
+                    } else {
+                        TextInput::single_line()
+                    }
+                })
+                .collect(),
+            edited: vec![false; Field::ALL.len()],
+            draft: false,
+        };
+        form.set(Field::SourceBranch, source_branch);
+        form.set(Field::TargetBranch, target_branch);
+        form
+    }
+
+    pub fn input(&self, field: Field) -> &TextInput {
+        &self.inputs[field.index()]
+    }
+
+    pub fn value(&self, field: Field) -> String {
+        self.input(field).text()
+    }
+
+    /// Whether the user changed `field` since the form was created or
+    /// cleared. Fields set by the app, such as the target branch from a
+    /// sync, don't count.
+    pub fn is_edited(&self, field: Field) -> bool {
+        self.edited[field.index()]
+    }
+
+    /// Replaces a field. Line breaks are dropped from single line fields.
+    pub fn set(&mut self, field: Field, text: &str) {
+        self.inputs[field.index()].set_text(text);
+    }
+
+    /// Passes a key press to the editor of `field` and returns whether the
+    /// text changed.
+    pub fn input_key(&mut self, field: Field, key: KeyEvent) -> bool {
+        let changed = self.inputs[field.index()].input(key);
+        self.edited[field.index()] |= changed;
+        changed
+    }
+
+    pub fn paste(&mut self, field: Field, text: &str) -> bool {
+        let changed = self.inputs[field.index()].paste(text);
+        self.edited[field.index()] |= changed;
+        changed
+    }
+
+    pub fn scroll(&mut self, field: Field, lines: i16) {
+        self.inputs[field.index()].scroll(lines);
+    }
+
+    /// Clears what was written, keeping the branches for the next pull
+    /// request.
+    pub fn clear(&mut self) {
+        self.set(Field::Title, "");
+        self.set(Field::Description, "");
+        self.edited[Field::Title.index()] = false;
+        self.edited[Field::Description.index()] = false;
+        self.draft = false;
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.value(Field::Title).is_empty() && self.value(Field::Description).is_empty()
+    }
+
+    /// Lines in the description editor, including a trailing empty one.
+    pub fn description_lines(&self) -> usize {
+        self.input(Field::Description).text_area().lines().len()
+    }
+
+    /// The pull request as written, checked for what GitHub would reject.
+    pub fn validate(&self) -> Result<PullRequest, PullRequestError> {
+        let pull_request = PullRequest {
+            title: self.value(Field::Title).trim().to_string(),
+            description: self.value(Field::Description),
+            source_branch: self.value(Field::SourceBranch).trim().to_string(),
+            target_branch: self.value(Field::TargetBranch).trim().to_string(),
+            draft: self.draft,
+        };
+        let invalid = |message: &str| Err(PullRequestError::InvalidInput(message.to_string()));
+        if pull_request.title.is_empty() {
+            return invalid("Title is empty");
+        }
+        if pull_request.source_branch.is_empty() {
+            return invalid("Source branch is empty");
+        }
+        if pull_request.target_branch.is_empty() {
+            return invalid("Target branch is empty");
+        }
+        if pull_request.source_branch == pull_request.target_branch {
+            return invalid("Source and target branch are the same");
+        }
+        Ok(pull_request)
+    }
+}
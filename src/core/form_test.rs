@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::errors::PullRequestError;
+    use crate::core::field::Field;
+    use crate::core::form::PullRequestForm;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(form: &mut PullRequestForm, field: Field, text: &str) {
+        for c in text.chars() {
+            form.input_key(field, key(KeyCode::Char(c)));
+        }
+    }
+
+    fn invalid_message(form: &PullRequestForm) -> String {
+        match form.validate() {
+            Err(PullRequestError::InvalidInput(message)) => message,
+            other => panic!("expected invalid input, got {:?}", other.map(|pr| pr.title)),
+        }
+    }
+
+    #[test]
+    fn test_new_form() {
+        let form = PullRequestForm::new("feature", "main");
+        assert_eq!(form.value(Field::SourceBranch), "feature");
+        assert_eq!(form.value(Field::TargetBranch), "main");
+        assert!(form.is_blank());
+        assert!(!form.draft);
+        assert_eq!(form.description_lines(), 1);
+    }
+
+    #[test]
+    fn test_typing_and_backspace() {
+        let mut form = PullRequestForm::new("feature", "main");
+        type_text(&mut form, Field::Title, "Add formm");
+        form.input_key(Field::Title, key(KeyCode::Backspace));
+        assert_eq!(form.value(Field::Title), "Add form");
+        assert!(!form.is_blank());
+
+        form.input_key(Field::TargetBranch, key(KeyCode::Backspace));
+        form.input_key(Field::TargetBranch, key(KeyCode::Backspace));
+        type_text(&mut form, Field::TargetBranch, "ster");
+        assert_eq!(form.value(Field::TargetBranch), "master");
+    }
+
+    #[test]
+    fn test_description_newlines() {
+        let mut form = PullRequestForm::new("feature", "main");
+        type_text(&mut form, Field::Description, "Line one");
+        form.input_key(Field::Description, key(KeyCode::Enter));
+        form.input_key(Field::Description, key(KeyCode::Enter));
+        type_text(&mut form, Field::Description, "Line three");
+        assert_eq!(form.value(Field::Description), "Line one\n\nLine three");
+        assert_eq!(form.description_lines(), 3);
+
+        form.input_key(Field::Description, key(KeyCode::Enter));
+        assert_eq!(form.value(Field::Description), "Line one\n\nLine three\n");
+        assert_eq!(form.description_lines(), 4);
+    }
+
+    #[test]
+    fn test_single_line_fields_ignore_enter() {
+        let mut form = PullRequestForm::new("feature", "main");
+        type_text(&mut form, Field::Title, "Title");
+        assert!(!form.input_key(Field::Title, key(KeyCode::Enter)));
+        assert_eq!(form.value(Field::Title), "Title");
+    }
+
+    #[test]
+    fn test_paste_and_set() {
+        let mut form = PullRequestForm::new("feature", "main");
+        form.paste(Field::Description, "## Summary\r\n\r\nDetails");
+        assert_eq!(form.value(Field::Description), "## Summary\n\nDetails");
+        form.paste(Field::Title, "Fix\nparser");
+        assert_eq!(form.value(Field::Title), "Fix parser");
+
+        form.set(Field::SourceBranch, "other\n");
+        assert_eq!(form.value(Field::SourceBranch), "other");
+        form.set(Field::Description, "Replaced");
+        assert_eq!(form.value(Field::Description), "Replaced");
+    }
+
+    #[test]
+    fn test_clear_keeps_branches() {
+        let mut form = PullRequestForm::new("feature", "main");
+        form.set(Field::Title, "Title");
+        form.set(Field::Description, "Body");
+        form.draft = true;
+        form.clear();
+        assert!(form.is_blank());
+        assert!(!form.draft);
+        assert_eq!(form.value(Field::SourceBranch), "feature");
+        assert_eq!(form.value(Field::TargetBranch), "main");
+    }
+
+    #[test]
+    fn test_edited_fields() {
+        let mut form = PullRequestForm::new("feature", "main");
+        form.set(Field::Title, "Title");
+        assert!(!form.is_edited(Field::Title));
+
+        type_text(&mut form, Field::TargetBranch, "x");
+        form.paste(Field::Title, " more");
+        assert!(form.is_edited(Field::TargetBranch));
+        assert!(form.is_edited(Field::Title));
+
+        form.clear();
+        assert!(!form.is_edited(Field::Title));
+        assert!(form.is_edited(Field::TargetBranch));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut form = PullRequestForm::new(" feature ", "main");
+        assert_eq!(invalid_message(&form), "Title is empty");
+
+        form.set(Field::Title, "  Add form  ");
+        form.set(Field::Description, "Body\n");
+        form.draft = true;
+        let pull_request = form.validate().unwrap();
+        assert_eq!(pull_request.title, "Add form");
+        assert_eq!(pull_request.description, "Body\n");
+        assert_eq!(pull_request.source_branch, "feature");
+        assert_eq!(pull_request.target_branch, "main");
+        assert!(pull_request.draft);
+
+        form.set(Field::SourceBranch, "");
+        assert_eq!(invalid_message(&form), "Source branch is empty");
+        form.set(Field::SourceBranch, "main");
+        assert_eq!(
+            invalid_message(&form),
+            "Source and target branch are the same"
+        );
+        form.set(Field::TargetBranch, " ");
+        assert_eq!(invalid_message(&form), "Target branch is empty");
+    }
+}
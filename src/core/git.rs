@@ -7,10 +7,10 @@ pub fn get_repo_info() -> Option<(String, String)> {
         .arg("--get")
         .arg("remote.origin.url")
         .output()
-        .expect("Failed to execute git command");
+        .ok()?;
 
     if output.status.success() {
-        let url = str::from_utf8(&output.stdout).unwrap().trim();
+        let url = str::from_utf8(&output.stdout).ok()?.trim();
         if let Some((owner, repo)) = parse_git_url(url) {
             return Some((owner.to_string(), repo.to_string()));
         }
@@ -42,10 +42,10 @@ pub fn get_current_branch() -> Option<String> {
         .arg("--abbrev-ref")
         .arg("HEAD")
         .output()
-        .expect("Failed to execute git command");
+        .ok()?;
 
     if output.status.success() {
-        let branch = str::from_utf8(&output.stdout).unwrap().trim();
+        let branch = str::from_utf8(&output.stdout).ok()?.trim();
         return Some(branch.to_string());
     }
 
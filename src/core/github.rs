@@ -1,3 +1,4 @@
+#[derive(Clone)]
 pub struct GithubRepository {
     url: String,
     default_branch: String,
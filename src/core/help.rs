@@ -0,0 +1,42 @@
+use crate::core::config::config_path;
+use crate::core::keymap::{Context, Keymap};
+use std::env;
+use std::path::Path;
+
+/// Where the config, and with it the token, is read from, with the home
+/// directory written as `~`.
+pub fn config_location() -> String {
+    let path = config_path();
+    let home = env::var("HOME").unwrap_or_default();
+    match path.strip_prefix(&home) {
+        Ok(relative) if !home.is_empty() => Path::new("~").join(relative).display().to_string(),
+        _ => path.display().to_string(),
+    }
+}
+
+/// Every key of `context` with what it does, from the same table the keys
+/// are looked up in. While typing, keys that would be typed are left out.
+pub fn bindings(keymap: &Keymap, context: Context) -> Vec<(String, String)> {
+    context
+        .actions()
+        .iter()
+        .filter_map(|&action| {
+            let keys: Vec<String> = keymap
+                .keys(action)
+                .iter()
+                .filter(|key| !(context.is_typing() && key.is_printable()))
+                .map(|key| key.to_string())
+                .collect();
+            (!keys.is_empty()).then(|| (keys.join(", "), action.description().to_string()))
+        })
+        .collect()
+}
+
+/// `api.github.com` for `https://api.github.com/`.
+pub fn host(api_url: &str) -> &str {
+    let url = api_url
+        .strip_prefix("https://")
+        .or_else(|| api_url.strip_prefix("http://"))
+        .unwrap_or(api_url);
+    url.split('/').next().unwrap_or(url)
+}
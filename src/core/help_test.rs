@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::help::{bindings, host};
+    use crate::core::keymap::{Action, Context, Keymap, KeymapConfig, Keys};
+
+    fn has(bindings: &[(String, String)], keys: &str, description: &str) -> bool {
+        bindings.iter().any(|(k, d)| k == keys && d == description)
+    }
+
+    #[test]
+    fn test_bindings_of_each_context() {
+        let keymap = Keymap::default();
+        let normal = bindings(&keymap, Context::Normal);
+        assert!(has(&normal, "s", Action::Sync.description()));
+        assert!(!has(&normal, "Enter", Action::Submit.description()));
+
+        let dashboard = bindings(&keymap, Context::Dashboard);
+        assert!(has(&dashboard, "Enter", Action::Open.description()));
+        assert!(!has(&dashboard, "n", Action::NewPullRequest.description()));
+
+        let inbox = bindings(&keymap, Context::Inbox);
+        assert!(has(&inbox, "m", "Mark as read"));
+        let messages = bindings(&keymap, Context::Messages);
+        assert!(has(&messages, "E", Action::CopyLastError.description()));
+    }
+
+    #[test]
+    fn test_bindings_follow_the_keymap() {
+        let mut config = KeymapConfig::default();
+        config
+            .bindings
+            .insert("sync".to_string(), Keys::One("S".to_string()));
+        let (keymap, _) = Keymap::from_config(&config);
+        let normal = bindings(&keymap, Context::Normal);
+        assert!(has(&normal, "S", "Sync with GitHub"));
+        assert!(has(&normal, "?, F1", Action::ShowHelp.description()));
+        // Not bound by default.
+        assert!(!normal
+            .iter()
+            .any(|(_, d)| d == Action::CommandLine.description()));
+
+        let timeline = bindings(&keymap, Context::Timeline);
+        assert!(has(
+            &timeline,
+            "x",
+            "Resolve or unresolve the selected thread"
+        ));
+        assert!(has(&timeline, "3", "React with 😄"));
+    }
+
+    #[test]
+    fn test_bindings_while_typing() {
+        let editing = bindings(&Keymap::default(), Context::Editing);
+        assert!(has(&editing, "F1", Action::ShowHelp.description()));
+        assert!(has(&editing, "Tab", "Move to the next field"));
+
+        let reply = bindings(&Keymap::default(), Context::Reply);
+        assert!(has(&reply, "Ctrl+S", "Send the reply"));
+        assert!(has(&reply, "F1", Action::ShowHelp.description()));
+    }
+
+    #[test]
+    fn test_host() {
+        assert_eq!(host("https://api.github.com"), "api.github.com");
+        assert_eq!(
+            host("https://github.example.com/api/v3"),
+            "github.example.com"
+        );
+        assert_eq!(host("localhost:8080"), "localhost:8080");
+    }
+}
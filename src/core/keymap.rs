@@ -0,0 +1,825 @@
+use crate::core::timeline::REACTIONS;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Where a key press is interpreted. Keys only conflict within a context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    /// The main screen.
+    Normal,
+    /// Typing into the form. Plain characters always go to the field.
+    Editing,
+    /// The confirmation popup before a pull request is sent.
+    Confirm,
+    Inbox,
+    Dashboard,
+    Timeline,
+    /// Writing a reply on the timeline. Plain characters go to the reply.
+    Reply,
+    Messages,
+    /// The help overlay.
+    Help,
+}
+
+impl Context {
+    pub const ALL: [Context; 9] = [
+        Context::Normal,
+        Context::Editing,
+        Context::Confirm,
+        Context::Inbox,
+        Context::Dashboard,
+        Context::Timeline,
+        Context::Reply,
+        Context::Messages,
+        Context::Help,
+    ];
+
+    /// Whether plain characters are typed instead of looked up.
+    pub fn is_typing(self) -> bool {
+        matches!(self, Context::Editing | Context::Reply)
+    }
+
+    /// The actions keys run in the context, in registry order.
+    pub fn actions(self) -> &'static [Action] {
+        match self {
+            Context::Normal => &[
+                Action::Quit,
+                Action::Edit,
+                Action::NewPullRequest,
+                Action::Sync,
+                Action::UpdateBranch,
+                Action::SelectNext,
+                Action::SelectPrevious,
+                Action::Open,
+                Action::ShowTimeline,
+                Action::RerunChecks,
+                Action::TogglePreview,
+                Action::OpenDashboard,
+                Action::OpenInbox,
+                Action::ChooseRepository,
+                Action::OpenInBrowser,
+                Action::OpenRepository,
+                Action::OpenCompare,
+                Action::CopyUrl,
+                Action::CopyRepositoryUrl,
+                Action::ShowMessages,
+                Action::ScrollMessagesUp,
+                Action::ScrollMessagesDown,
+                Action::CopyLastError,
+                Action::CommandLine,
+                Action::CommandPalette,
+                Action::ShowHelp,
+            ],
+            Context::Editing => &[
+                Action::ShowHelp,
+                Action::StopEditing,
+                Action::NextField,
+                Action::PreviousField,
+                Action::Submit,
+                Action::OpenEditor,
+            ],
+            Context::Confirm => &[
+                Action::CommandLine,
+                Action::CommandPalette,
+                Action::ShowHelp,
+                Action::Confirm,
+                Action::ContinueEditing,
+                Action::Cancel,
+            ],
+            Context::Inbox => &[
+                Action::Quit,
+                Action::Sync,
+                Action::SelectNext,
+                Action::SelectPrevious,
+                Action::Open,
+                Action::OpenInBrowser,
+                Action::CommandLine,
+                Action::CommandPalette,
+                Action::ShowHelp,
+                Action::MarkRead,
+            ],
+            Context::Dashboard => &[
+                Action::Quit,
+                Action::Sync,
+                Action::SelectNext,
+                Action::SelectPrevious,
+                Action::Open,
+                Action::OpenInBrowser,
+                Action::CommandLine,
+                Action::CommandPalette,
+                Action::ShowHelp,
+            ],
+            Context::Timeline => &[
+                Action::Quit,
+                Action::Sync,
+                Action::SelectNext,
+                Action::SelectPrevious,
+                Action::OpenInBrowser,
+                Action::CommandLine,
+                Action::CommandPalette,
+                Action::ShowHelp,
+                Action::Reply,
+                Action::ReactThumbsUp,
+                Action::ReactThumbsDown,
+                Action::ReactLaugh,
+                Action::ReactConfused,
+                Action::ReactHeart,
+                Action::ReactHooray,
+                Action::ReactRocket,
+                Action::ReactEyes,
+                Action::ResolveThread,
+            ],
+            Context::Reply => &[Action::ShowHelp, Action::SendReply, Action::CancelReply],
+            Context::Messages => &[
+                Action::Quit,
+                Action::SelectNext,
+                Action::SelectPrevious,
+                Action::ShowMessages,
+                Action::ScrollMessagesUp,
+                Action::ScrollMessagesDown,
+                Action::CopyLastError,
+                Action::CommandLine,
+                Action::CommandPalette,
+                Action::ShowHelp,
+            ],
+            Context::Help => &[
+                Action::Quit,
+                Action::SelectNext,
+                Action::SelectPrevious,
+                Action::CommandLine,
+                Action::CommandPalette,
+                Action::ShowHelp,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    Quit,
+    Edit,
+    NewPullRequest,
+    Sync,
+    UpdateBranch,
+    SelectNext,
+    SelectPrevious,
+    Open,
+    ShowTimeline,
+    RerunChecks,
+    TogglePreview,
+    OpenDashboard,
+    OpenInbox,
+    ChooseRepository,
+    OpenInBrowser,
+    OpenRepository,
+    OpenCompare,
+    CopyUrl,
+    CopyRepositoryUrl,
+    ShowMessages,
+    ScrollMessagesUp,
+    ScrollMessagesDown,
+    CopyLastError,
+    CommandLine,
+    CommandPalette,
+    ShowHelp,
+    StopEditing,
+    NextField,
+    PreviousField,
+    Submit,
+    OpenEditor,
+    Confirm,
+    ContinueEditing,
+    Cancel,
+    MarkRead,
+    Reply,
+    ReactThumbsUp,
+    ReactThumbsDown,
+    ReactLaugh,
+    ReactConfused,
+    ReactHeart,
+    ReactHooray,
+    ReactRocket,
+    ReactEyes,
+    ResolveThread,
+    SendReply,
+    CancelReply,
+}
+
+impl Action {
+    pub const ALL: [Action; 47] = [
+        Action::Quit,
+        Action::Edit,
+        Action::NewPullRequest,
+        Action::Sync,
+        Action::UpdateBranch,
+        Action::SelectNext,
+        Action::SelectPrevious,
+        Action::Open,
+        Action::ShowTimeline,
+        Action::RerunChecks,
+        Action::TogglePreview,
+        Action::OpenDashboard,
+        Action::OpenInbox,
+        Action::ChooseRepository,
+        Action::OpenInBrowser,
+        Action::OpenRepository,
+        Action::OpenCompare,
+        Action::CopyUrl,
+        Action::CopyRepositoryUrl,
+        Action::ShowMessages,
+        Action::ScrollMessagesUp,
+        Action::ScrollMessagesDown,
+        Action::CopyLastError,
+        Action::CommandLine,
+        Action::CommandPalette,
+        Action::ShowHelp,
+        Action::StopEditing,
+        Action::NextField,
+        Action::PreviousField,
+        Action::Submit,
+        Action::OpenEditor,
+        Action::Confirm,
+        Action::ContinueEditing,
+        Action::Cancel,
+        Action::MarkRead,
+        Action::Reply,
+        Action::ReactThumbsUp,
+        Action::ReactThumbsDown,
+        Action::ReactLaugh,
+        Action::ReactConfused,
+        Action::ReactHeart,
+        Action::ReactHooray,
+        Action::ReactRocket,
+        Action::ReactEyes,
+        Action::ResolveThread,
+        Action::SendReply,
+        Action::CancelReply,
+    ];
+
+    /// The reactions in the order of `REACTIONS`.
+    pub const REACTIONS: [Action; 8] = [
+        Action::ReactThumbsUp,
+        Action::ReactThumbsDown,
+        Action::ReactLaugh,
+        Action::ReactConfused,
+        Action::ReactHeart,
+        Action::ReactHooray,
+        Action::ReactRocket,
+        Action::ReactEyes,
+    ];
+
+    /// The reaction content, such as `+1`, a react action adds.
+    pub fn reaction(self) -> Option<&'static str> {
+        Action::REACTIONS
+            .iter()
+            .position(|action| *action == self)
+            .map(|index| REACTIONS[index])
+    }
+
+    /// The name used in the `[keymap]` config section and on the command
+    /// line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Edit => "edit",
+            Action::NewPullRequest => "new_pull_request",
+            Action::Sync => "sync",
+            Action::UpdateBranch => "update_branch",
+            Action::SelectNext => "select_next",
+            Action::SelectPrevious => "select_previous",
+            Action::Open => "open",
+            Action::ShowTimeline => "show_timeline",
+            Action::RerunChecks => "rerun_checks",
+            Action::TogglePreview => "toggle_preview",
+            Action::OpenDashboard => "open_dashboard",
+            Action::OpenInbox => "open_inbox",
+            Action::ChooseRepository => "choose_repository",
+            Action::OpenInBrowser => "open_in_browser",
+            Action::OpenRepository => "open_repository",
+            Action::OpenCompare => "open_compare",
+            Action::CopyUrl => "copy_url",
+            Action::CopyRepositoryUrl => "copy_repository_url",
+            Action::ShowMessages => "show_messages",
+            Action::ScrollMessagesUp => "scroll_messages_up",
+            Action::ScrollMessagesDown => "scroll_messages_down",
+            Action::CopyLastError => "copy_last_error",
+            Action::CommandLine => "command_line",
+            Action::CommandPalette => "command_palette",
+            Action::ShowHelp => "show_help",
+            Action::StopEditing => "stop_editing",
+            Action::NextField => "next_field",
+            Action::PreviousField => "previous_field",
+            Action::Submit => "submit",
+            Action::OpenEditor => "open_editor",
+            Action::Confirm => "confirm",
+            Action::ContinueEditing => "continue_editing",
+            Action::Cancel => "cancel",
+            Action::MarkRead => "mark_read",
+            Action::Reply => "reply",
+            Action::ReactThumbsUp => "react_thumbs_up",
+            Action::ReactThumbsDown => "react_thumbs_down",
+            Action::ReactLaugh => "react_laugh",
+            Action::ReactConfused => "react_confused",
+            Action::ReactHeart => "react_heart",
+            Action::ReactHooray => "react_hooray",
+            Action::ReactRocket => "react_rocket",
+            Action::ReactEyes => "react_eyes",
+            Action::ResolveThread => "resolve_thread",
+            Action::SendReply => "send_reply",
+            Action::CancelReply => "cancel_reply",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit, or go back to the main screen",
+            Action::Edit => "Edit the pull request",
+            Action::NewPullRequest => "Create a new pull request",
+            Action::Sync => "Sync with GitHub",
+            Action::UpdateBranch => "Update the branch with the target branch",
+            Action::SelectNext => "Select the next item",
+            Action::SelectPrevious => "Select the previous item",
+            Action::Open => "Open the selected item",
+            Action::ShowTimeline => "Show the pull request timeline",
+            Action::RerunChecks => "Re-run failed checks",
+            Action::TogglePreview => "Toggle the description preview",
+            Action::OpenDashboard => "Open the dashboard",
+            Action::OpenInbox => "Open the inbox",
+            Action::ChooseRepository => "Choose a repository",
+            Action::OpenInBrowser => "Open the pull request in the browser",
+            Action::OpenRepository => "Open the repository in the browser",
+            Action::OpenCompare => "Open the comparison in the browser",
+            Action::CopyUrl => "Copy the pull request URL",
+            Action::CopyRepositoryUrl => "Copy the repository URL",
+            Action::ShowMessages => "Show the message log",
+            Action::ScrollMessagesUp => "Scroll back through the messages",
+            Action::ScrollMessagesDown => "Scroll forward through the messages",
+            Action::CopyLastError => "Copy the last error",
+            Action::CommandLine => "Run a command by name",
+            Action::CommandPalette => "Search the actions",
+            Action::ShowHelp => "Show every key and the account in use",
+            Action::StopEditing => "Stop editing",
+            Action::NextField => "Move to the next field",
+            Action::PreviousField => "Move to the previous field",
+            Action::Submit => "Review and send the pull request",
+            Action::OpenEditor => "Write the field in $EDITOR",
+            Action::Confirm => "Send the pull request",
+            Action::ContinueEditing => "Continue editing",
+            Action::Cancel => "Cancel",
+            Action::MarkRead => "Mark as read",
+            Action::Reply => "Reply to the selected comment",
+            Action::ReactThumbsUp => "React with 👍",
+            Action::ReactThumbsDown => "React with 👎",
+            Action::ReactLaugh => "React with 😄",
+            Action::ReactConfused => "React with 😕",
+            Action::ReactHeart => "React with ❤️",
+            Action::ReactHooray => "React with 🎉",
+            Action::ReactRocket => "React with 🚀",
+            Action::ReactEyes => "React with 👀",
+            Action::ResolveThread => "Resolve or unresolve the selected thread",
+            Action::SendReply => "Send the reply",
+            Action::CancelReply => "Cancel the reply",
+        }
+    }
+
+    /// Short names accepted on the command line besides `name`.
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::Edit => &["e"],
+            Action::NewPullRequest => &["new", "n"],
+            Action::Sync => &["s"],
+            Action::ShowTimeline => &["timeline"],
+            Action::OpenDashboard => &["dashboard"],
+            Action::OpenInbox => &["inbox"],
+            Action::ChooseRepository => &["repo"],
+            Action::TogglePreview => &["preview"],
+            Action::CommandPalette => &["palette"],
+            Action::ShowMessages => &["messages", "log"],
+            Action::ShowHelp => &["help", "h"],
+            _ => &[],
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    /// The action of `context` a command line entry such as `q` or `sync`
+    /// runs.
+    pub fn from_command(command: &str, context: Context) -> Option<Action> {
+        let command = command.trim();
+        context
+            .actions()
+            .iter()
+            .copied()
+            .find(|action| action.name() == command || action.aliases().contains(&command))
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["Esc"],
+            Action::Edit => &["e"],
+            Action::NewPullRequest => &["n"],
+            Action::Sync => &["s"],
+            Action::UpdateBranch => &["u"],
+            Action::SelectNext => &["Down"],
+            Action::SelectPrevious => &["Up"],
+            Action::Open => &["Enter"],
+            Action::ShowTimeline => &["t"],
+            Action::RerunChecks => &["R"],
+            Action::TogglePreview => &["p"],
+            Action::OpenDashboard => &["d"],
+            Action::OpenInbox => &["i"],
+            Action::ChooseRepository => &["r"],
+            Action::OpenInBrowser => &["o"],
+            Action::OpenRepository => &["b"],
+            Action::OpenCompare => &["c"],
+            Action::CopyUrl => &["y"],
+            Action::CopyRepositoryUrl => &["Y"],
+            Action::ShowMessages => &["l"],
+            Action::ScrollMessagesUp => &["PageUp"],
+            Action::ScrollMessagesDown => &["PageDown"],
+            Action::CopyLastError => &["E"],
+            Action::CommandLine => &[],
+            Action::CommandPalette => &["Ctrl+p"],
+            Action::ShowHelp => &["?", "F1"],
+            Action::StopEditing => &["Esc"],
+            Action::NextField => &["Tab"],
+            Action::PreviousField => &["BackTab"],
+            Action::Submit => &["Enter"],
+            Action::OpenEditor => &["Ctrl+e"],
+            Action::Confirm => &["Enter", "y"],
+            Action::ContinueEditing => &["e", "n"],
+            Action::Cancel => &["Esc"],
+            Action::MarkRead => &["m"],
+            Action::Reply => &["c"],
+            Action::ReactThumbsUp => &["1"],
+            Action::ReactThumbsDown => &["2"],
+            Action::ReactLaugh => &["3"],
+            Action::ReactConfused => &["4"],
+            Action::ReactHeart => &["5"],
+            Action::ReactHooray => &["6"],
+            Action::ReactRocket => &["7"],
+            Action::ReactEyes => &["8"],
+            Action::ResolveThread => &["x"],
+            Action::SendReply => &["Ctrl+s"],
+            Action::CancelReply => &["Esc"],
+        }
+    }
+}
+
+/// A key with its modifiers, written as `e`, `R`, `Ctrl+e`, `Alt+x`, `Esc`
+/// or `Down`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> KeyBinding {
+        let code = match code {
+            // Terminals send Ctrl and Alt letters in either case, and
+            // `Ctrl+E` is written for `Ctrl+e`.
+            KeyCode::Char(c) if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            code => code,
+        };
+        KeyBinding {
+            code,
+            // Shift is part of the character (`R`) or of the key (`BackTab`).
+            modifiers: modifiers - KeyModifiers::SHIFT,
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<KeyBinding, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text.trim();
+        while let Some((modifier, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("Unknown modifier \"{}\" in \"{}\"", modifier, text)),
+            };
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                if !c.is_ascii_alphabetic() {
+                    return Err(format!(
+                        "Shift can't be combined with \"{}\" in \"{}\", write the character it types",
+                        c, text
+                    ));
+                }
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                key => match key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("Unknown key \"{}\"", text)),
+                },
+            },
+        };
+        Ok(KeyBinding::new(code, modifiers))
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        *self == KeyBinding::new(key.code, key.modifiers)
+    }
+
+    /// Whether the key would type a character into a text field.
+    pub fn is_printable(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if !self.modifiers.is_empty() => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// The `[keymap]` config section: a preset and the keys of any action to
+/// change, as a key or a list of keys.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct KeymapConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(flatten)]
+    pub bindings: BTreeMap<String, Keys>,
+}
+
+impl KeymapConfig {
+    pub fn is_empty(&self) -> bool {
+        self.preset.is_none() && self.bindings.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    Default,
+    Vim,
+    Emacs,
+}
+
+impl Preset {
+    pub fn from_name(name: &str) -> Option<Preset> {
+        match name.to_lowercase().as_str() {
+            "default" => Some(Preset::Default),
+            "vim" => Some(Preset::Vim),
+            "emacs" => Some(Preset::Emacs),
+            _ => None,
+        }
+    }
+
+    /// Keys the preset uses instead of the defaults.
+    fn overrides(self) -> &'static [(Action, &'static [&'static str])] {
+        match self {
+            Preset::Default => &[],
+            Preset::Vim => &[
+                (Action::Quit, &["Esc", "q"]),
+                (Action::SelectNext, &["j", "Down"]),
+                (Action::SelectPrevious, &["k", "Up"]),
+                (Action::CommandLine, &[":"]),
+            ],
+            Preset::Emacs => &[
+                (Action::Quit, &["Esc", "Ctrl+g"]),
+                (Action::SelectNext, &["Ctrl+n", "Down"]),
+                (Action::SelectPrevious, &["Ctrl+p", "Up"]),
+                (Action::CommandLine, &["Alt+x"]),
+                (Action::CommandPalette, &["Alt+p"]),
+                (Action::StopEditing, &["Esc", "Ctrl+g"]),
+                (Action::Cancel, &["Esc", "Ctrl+g"]),
+                (Action::CancelReply, &["Esc", "Ctrl+g"]),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: BTreeMap<Action, Vec<KeyBinding>>,
+}
+
+impl Keymap {
+    pub fn preset(preset: Preset) -> Keymap {
+        let bindings = Action::ALL
+            .into_iter()
+            .map(|action| {
+                let keys = preset
+                    .overrides()
+                    .iter()
+                    .find(|(overridden, _)| *overridden == action)
+                    .map_or(action.default_keys(), |(_, keys)| *keys);
+                let keys = keys
+                    .iter()
+                    .map(|key| KeyBinding::parse(key).expect("preset keys are valid"))
+                    .collect();
+                (action, keys)
+            })
+            .collect();
+        Keymap { bindings }
+    }
+
+    /// Builds the keymap described by the config, with a warning for every
+    /// unknown preset, action or key and for every conflict.
+    pub fn from_config(config: &KeymapConfig) -> (Keymap, Vec<String>) {
+        let mut warnings = Vec::new();
+        let preset = match config.preset.as_deref() {
+            None => Preset::Default,
+            Some(name) => Preset::from_name(name).unwrap_or_else(|| {
+                warnings.push(format!(
+                    "Unknown keymap preset \"{}\", use default, vim or emacs",
+                    name
+                ));
+                Preset::Default
+            }),
+        };
+        let mut keymap = Keymap::preset(preset);
+
+        for (name, keys) in &config.bindings {
+            let Some(action) = Action::from_name(name) else {
+                warnings.push(format!("Unknown action \"{}\" in the keymap", name));
+                continue;
+            };
+            let keys = match keys {
+                Keys::One(key) => std::slice::from_ref(key),
+                Keys::Many(keys) => keys.as_slice(),
+            };
+            let mut bindings = Vec::new();
+            for key in keys {
+                match KeyBinding::parse(key) {
+                    Ok(binding) => bindings.push(binding),
+                    Err(e) => warnings.push(e),
+                }
+            }
+            keymap.bindings.insert(action, bindings);
+        }
+
+        warnings.extend(keymap.conflicts());
+        (keymap, warnings)
+    }
+
+    /// Keys bound to more than one action of the same context, and keys of
+    /// actions that only run while typing, which would be typed instead.
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        for context in Context::ALL {
+            let actions = context.actions();
+            for (index, action) in actions.iter().enumerate() {
+                for key in self.keys(*action) {
+                    let typed = context.is_typing()
+                        && key.is_printable()
+                        && Context::ALL
+                            .iter()
+                            .all(|other| other.is_typing() || !other.actions().contains(action));
+                    let mut found = Vec::new();
+                    if typed {
+                        found.push(format!(
+                            "{} is typed into the fields, bind {} to a key with Ctrl or Alt",
+                            key,
+                            action.name()
+                        ));
+                    }
+                    let other = actions[index + 1..]
+                        .iter()
+                        .find(|other| self.keys(**other).contains(key));
+                    if let Some(other) = other {
+                        found.push(format!(
+                            "{} is bound to both {} and {}",
+                            key,
+                            action.name(),
+                            other.name()
+                        ));
+                    }
+                    for conflict in found {
+                        if !conflicts.contains(&conflict) {
+                            conflicts.push(conflict);
+                        }
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// The action `key` triggers in `context`. While typing, keys that type
+    /// a character don't trigger anything.
+    pub fn action(&self, context: Context, key: &KeyEvent) -> Option<Action> {
+        self.find_action(context, key, context.is_typing())
+    }
+
+    /// The action `key` triggers in `context` from a popup that takes text,
+    /// where keys that type a character are typed instead.
+    pub fn typing_action(&self, context: Context, key: &KeyEvent) -> Option<Action> {
+        self.find_action(context, key, true)
+    }
+
+    fn find_action(&self, context: Context, key: &KeyEvent, typing: bool) -> Option<Action> {
+        context.actions().iter().copied().find(|action| {
+            self.keys(*action)
+                .iter()
+                .any(|binding| binding.matches(key) && !(typing && binding.is_printable()))
+        })
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// `[k]/[j] to select`: the first key of each action, or `None` when
+    /// none of them is bound.
+    pub fn hint(&self, actions: &[Action], description: &str) -> Option<String> {
+        self.find_hint(actions, description, false)
+    }
+
+    /// Like `hint`, with the first key of each action that doesn't type a
+    /// character, for the form and the popups that take text.
+    pub fn typing_hint(&self, actions: &[Action], description: &str) -> Option<String> {
+        self.find_hint(actions, description, true)
+    }
+
+    fn find_hint(&self, actions: &[Action], description: &str, typing: bool) -> Option<String> {
+        let keys: Vec<String> = actions
+            .iter()
+            .filter_map(|action| {
+                self.keys(*action)
+                    .iter()
+                    .find(|key| !(typing && key.is_printable()))
+            })
+            .map(|key| format!("[{}]", key))
+            .collect();
+        if keys.is_empty() {
+            return None;
+        }
+        Some(format!("{} to {}", keys.join("/"), description))
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap::preset(Preset::Default)
+    }
+}
+
+/// Joins hints into `Press a, b or c`.
+pub fn instructions(hints: impl IntoIterator<Item = Option<String>>) -> String {
+    let hints: Vec<String> = hints.into_iter().flatten().collect();
+    match hints.split_last() {
+        None => String::new(),
+        Some((last, [])) => format!("Press {}", last),
+        Some((last, rest)) => format!("Press {} or {}", rest.join(", "), last),
+    }
+}
@@ -0,0 +1,327 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::keymap::{
+        instructions, Action, Context, KeyBinding, Keymap, KeymapConfig, Keys, Preset,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn config(preset: Option<&str>, bindings: &[(&str, Keys)]) -> KeymapConfig {
+        KeymapConfig {
+            preset: preset.map(str::to_string),
+            bindings: bindings
+                .iter()
+                .map(|(name, keys)| (name.to_string(), keys.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_key_binding() {
+        let binding = KeyBinding::parse("Ctrl+e").unwrap();
+        assert_eq!(
+            binding,
+            KeyBinding::new(KeyCode::Char('e'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(binding.to_string(), "Ctrl+E");
+
+        assert_eq!(
+            KeyBinding::parse("Shift+Tab").unwrap(),
+            KeyBinding::new(KeyCode::BackTab, KeyModifiers::NONE)
+        );
+        assert_eq!(KeyBinding::parse("down").unwrap().to_string(), "↓");
+        assert_eq!(KeyBinding::parse("F5").unwrap().to_string(), "F5");
+        assert_eq!(KeyBinding::parse("R").unwrap().to_string(), "R");
+        assert!(KeyBinding::parse("Hyper+x").is_err());
+    }
+
+    #[test]
+    fn test_parse_normalises_the_case_of_modified_keys() {
+        let binding = KeyBinding::parse("Ctrl+E").unwrap();
+        assert_eq!(binding, KeyBinding::parse("Ctrl+e").unwrap());
+        assert_eq!(KeyBinding::parse(&binding.to_string()).unwrap(), binding);
+        assert!(binding.matches(&key(KeyCode::Char('e'), KeyModifiers::CONTROL)));
+        assert!(binding.matches(&key(
+            KeyCode::Char('E'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT
+        )));
+        assert!(KeyBinding::parse("Alt+X")
+            .unwrap()
+            .matches(&key(KeyCode::Char('x'), KeyModifiers::ALT)));
+
+        assert_eq!(
+            KeyBinding::parse("Shift+a").unwrap(),
+            KeyBinding::parse("A").unwrap()
+        );
+        assert!(KeyBinding::parse("Shift+1").is_err());
+        assert!(KeyBinding::parse("nope").is_err());
+    }
+
+    #[test]
+    fn test_default_keymap() {
+        let keymap = Keymap::default();
+        assert!(keymap.conflicts().is_empty());
+        assert_eq!(
+            keymap.action(
+                Context::Normal,
+                &key(KeyCode::Char('s'), KeyModifiers::NONE)
+            ),
+            Some(Action::Sync)
+        );
+        assert_eq!(
+            keymap.action(
+                Context::Normal,
+                &key(KeyCode::Char('R'), KeyModifiers::SHIFT)
+            ),
+            Some(Action::RerunChecks)
+        );
+        assert_eq!(
+            keymap.action(
+                Context::Editing,
+                &key(KeyCode::Char('e'), KeyModifiers::CONTROL)
+            ),
+            Some(Action::OpenEditor)
+        );
+        assert_eq!(
+            keymap.action(
+                Context::Editing,
+                &key(KeyCode::Char('e'), KeyModifiers::NONE)
+            ),
+            None
+        );
+        assert_eq!(
+            keymap.action(Context::Confirm, &key(KeyCode::Esc, KeyModifiers::NONE)),
+            Some(Action::Cancel)
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        for preset in [Preset::Default, Preset::Vim, Preset::Emacs] {
+            assert!(Keymap::preset(preset).conflicts().is_empty());
+        }
+
+        let vim = Keymap::preset(Preset::Vim);
+        assert_eq!(
+            vim.action(
+                Context::Normal,
+                &key(KeyCode::Char('j'), KeyModifiers::NONE)
+            ),
+            Some(Action::SelectNext)
+        );
+        assert_eq!(
+            vim.action(
+                Context::Normal,
+                &key(KeyCode::Char(':'), KeyModifiers::NONE)
+            ),
+            Some(Action::CommandLine)
+        );
+
+        let emacs = Keymap::preset(Preset::Emacs);
+        assert_eq!(
+            emacs.action(Context::Normal, &key(KeyCode::Char('x'), KeyModifiers::ALT)),
+            Some(Action::CommandLine)
+        );
+        assert_eq!(
+            emacs.action(
+                Context::Editing,
+                &key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+            ),
+            Some(Action::StopEditing)
+        );
+        assert_eq!(Preset::from_name("VIM"), Some(Preset::Vim));
+        assert_eq!(Preset::from_name("nano"), None);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let (keymap, warnings) = Keymap::from_config(&config(
+            Some("vim"),
+            &[
+                ("sync", Keys::One("S".to_string())),
+                (
+                    "open_editor",
+                    Keys::Many(vec!["Ctrl+e".to_string(), "F2".to_string()]),
+                ),
+            ],
+        ));
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(
+            keymap.action(
+                Context::Normal,
+                &key(KeyCode::Char('S'), KeyModifiers::SHIFT)
+            ),
+            Some(Action::Sync)
+        );
+        assert_eq!(
+            keymap.action(
+                Context::Normal,
+                &key(KeyCode::Char('s'), KeyModifiers::NONE)
+            ),
+            None
+        );
+        assert_eq!(
+            keymap.action(Context::Editing, &key(KeyCode::F(2), KeyModifiers::NONE)),
+            Some(Action::OpenEditor)
+        );
+        assert_eq!(
+            keymap.action(
+                Context::Normal,
+                &key(KeyCode::Char('k'), KeyModifiers::NONE)
+            ),
+            Some(Action::SelectPrevious)
+        );
+    }
+
+    #[test]
+    fn test_from_config_warnings() {
+        let (keymap, warnings) = Keymap::from_config(&config(
+            Some("nano"),
+            &[
+                ("launch", Keys::One("l".to_string())),
+                ("sync", Keys::One("Hyper+s".to_string())),
+            ],
+        ));
+        assert_eq!(
+            warnings,
+            [
+                "Unknown keymap preset \"nano\", use default, vim or emacs",
+                "Unknown action \"launch\" in the keymap",
+                "Unknown modifier \"Hyper\" in \"Hyper+s\"",
+            ]
+        );
+        assert!(keymap.keys(Action::Sync).is_empty());
+        assert!(keymap.keys(Action::Edit).len() == 1);
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let (_, warnings) = Keymap::from_config(&config(
+            None,
+            &[
+                ("sync", Keys::One("e".to_string())),
+                ("next_field", Keys::One("j".to_string())),
+                ("confirm", Keys::One("e".to_string())),
+            ],
+        ));
+        assert_eq!(
+            warnings,
+            [
+                "e is bound to both edit and sync",
+                "j is typed into the fields, bind next_field to a key with Ctrl or Alt",
+                "e is bound to both confirm and continue_editing",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_screen_keys_go_through_the_keymap() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.action(
+                Context::Timeline,
+                &key(KeyCode::Char('c'), KeyModifiers::NONE)
+            ),
+            Some(Action::Reply)
+        );
+        assert_eq!(
+            keymap.action(
+                Context::Timeline,
+                &key(KeyCode::Char('3'), KeyModifiers::NONE)
+            ),
+            Some(Action::ReactLaugh)
+        );
+        assert_eq!(Action::ReactLaugh.reaction(), Some("laugh"));
+        assert_eq!(
+            keymap.action(
+                Context::Reply,
+                &key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            ),
+            Some(Action::SendReply)
+        );
+        assert_eq!(
+            keymap.action(Context::Reply, &key(KeyCode::Char('?'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(
+            keymap.action(Context::Inbox, &key(KeyCode::Char('m'), KeyModifiers::NONE)),
+            Some(Action::MarkRead)
+        );
+
+        let (_, warnings) = Keymap::from_config(&config(
+            None,
+            &[
+                ("reply", Keys::One("x".to_string())),
+                ("mark_read", Keys::One("s".to_string())),
+            ],
+        ));
+        assert_eq!(
+            warnings,
+            [
+                "s is bound to both sync and mark_read",
+                "x is bound to both reply and resolve_thread",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_command() {
+        let normal = |command| Action::from_command(command, Context::Normal);
+        assert_eq!(normal("sync"), Some(Action::Sync));
+        assert_eq!(normal(" q "), Some(Action::Quit));
+        assert_eq!(normal("new"), Some(Action::NewPullRequest));
+        assert_eq!(normal("submit"), None);
+        assert_eq!(normal("launch"), None);
+        assert_eq!(normal("mark_read"), None);
+
+        assert_eq!(
+            Action::from_command("mark_read", Context::Inbox),
+            Some(Action::MarkRead)
+        );
+        assert_eq!(Action::from_command("new", Context::Inbox), None);
+    }
+
+    #[test]
+    fn test_hints() {
+        let keymap = Keymap::preset(Preset::Vim);
+        assert_eq!(
+            keymap.hint(&[Action::SelectPrevious, Action::SelectNext], "select"),
+            Some("[k]/[j] to select".to_string())
+        );
+        assert_eq!(
+            keymap.hint(&[Action::OpenEditor], "open the editor"),
+            Some("[Ctrl+E] to open the editor".to_string())
+        );
+        assert_eq!(Keymap::default().hint(&[Action::CommandLine], "run"), None);
+        // Popups that take text skip the keys that would be typed.
+        assert_eq!(
+            keymap.typing_hint(&[Action::SelectPrevious, Action::SelectNext], "move"),
+            Some("[↑]/[↓] to move".to_string())
+        );
+        let down = key(KeyCode::Down, KeyModifiers::NONE);
+        let j = key(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(
+            keymap.typing_action(Context::Normal, &down),
+            Some(Action::SelectNext)
+        );
+        assert_eq!(keymap.typing_action(Context::Normal, &j), None);
+
+        assert_eq!(instructions([]), "");
+        assert_eq!(
+            instructions([Some("[a] to go".to_string())]),
+            "Press [a] to go"
+        );
+        assert_eq!(
+            instructions([
+                Some("[a] to go".to_string()),
+                None,
+                Some("[b] to stop".to_string()),
+                Some("[c] to quit".to_string()),
+            ]),
+            "Press [a] to go, [b] to stop or [c] to quit"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How many messages the log keeps before it drops the oldest.
+pub const MAX_MESSAGES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// How long the toast of a message stays up. Problems stay longer so
+    /// there is time to read them.
+    pub fn toast_duration(self) -> Duration {
+        match self {
+            Severity::Success => Duration::from_secs(4),
+            Severity::Warning | Severity::Error => Duration::from_secs(10),
+        }
+    }
+
+    /// A mark that tells the severities apart without colours.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Severity::Success => "✓",
+            Severity::Warning => "!",
+            Severity::Error => "✗",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub severity: Severity,
+    pub text: String,
+    /// When it was logged, shown next to it in the log.
+    pub time: DateTime<Local>,
+    /// When its toast went up.
+    pub shown: Instant,
+}
+
+impl Message {
+    pub fn new(severity: Severity, text: impl Into<String>) -> Message {
+        Message {
+            severity,
+            text: text.into(),
+            time: Local::now(),
+            shown: Instant::now(),
+        }
+    }
+}
+
+/// Everything the app reported this session, newest last. The newest
+/// message is also shown as a toast until it times out or is dismissed.
+#[derive(Debug, Clone, Default)]
+pub struct MessageLog {
+    messages: VecDeque<Message>,
+    dismissed: bool,
+    /// How many lines the log is scrolled back from the newest one.
+    pub scroll: usize,
+}
+
+impl MessageLog {
+    /// Logs `message` and scrolls back down so it can be seen.
+    pub fn push(&mut self, message: Message) {
+        if self.messages.len() == MAX_MESSAGES {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message);
+        self.dismissed = false;
+        self.scroll = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Message> {
+        self.messages.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn last(&self) -> Option<&Message> {
+        self.messages.back()
+    }
+
+    pub fn last_error(&self) -> Option<&Message> {
+        self.messages
+            .iter()
+            .rev()
+            .find(|message| message.severity == Severity::Error)
+    }
+
+    /// The newest message while its toast is up at `now`.
+    pub fn toast(&self, now: Instant) -> Option<&Message> {
+        if self.dismissed {
+            return None;
+        }
+        self.last()
+            .filter(|message| now.duration_since(message.shown) < message.severity.toast_duration())
+    }
+
+    /// Takes the toast down. The message stays in the log.
+    pub fn dismiss(&mut self) {
+        self.dismissed = true;
+    }
+
+    /// Scrolls back by `lines`. How far it can go depends on how the
+    /// messages wrap, so the caller caps it with `clamp_scroll`.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_add(lines);
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Keeps the scroll within `max` lines, the wrapped lines that don't
+    /// fit on screen.
+    pub fn clamp_scroll(&mut self, max: usize) {
+        self.scroll = self.scroll.min(max);
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::message_log::{Message, MessageLog, Severity, MAX_MESSAGES};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_push_keeps_the_newest_messages() {
+        let mut log = MessageLog::default();
+        for i in 0..MAX_MESSAGES + 2 {
+            log.push(Message::new(Severity::Success, format!("message {}", i)));
+        }
+        assert_eq!(log.iter().count(), MAX_MESSAGES);
+        assert_eq!(log.iter().next().unwrap().text, "message 2");
+        assert_eq!(
+            log.last().unwrap().text,
+            format!("message {}", MAX_MESSAGES + 1)
+        );
+    }
+
+    #[test]
+    fn test_last_error() {
+        let mut log = MessageLog::default();
+        assert!(log.last_error().is_none());
+        log.push(Message::new(Severity::Error, "Failed to sync"));
+        log.push(Message::new(Severity::Success, "Copied ✅"));
+        assert_eq!(log.last_error().unwrap().text, "Failed to sync");
+    }
+
+    #[test]
+    fn test_toasts_time_out() {
+        let mut log = MessageLog::default();
+        log.push(Message::new(Severity::Success, "Synced ✅"));
+        let shown = log.last().unwrap().shown;
+        assert!(log.toast(shown).is_some());
+        assert!(log.toast(shown + Duration::from_secs(3)).is_some());
+        assert!(log.toast(shown + Duration::from_secs(4)).is_none());
+
+        log.push(Message::new(Severity::Error, "Failed to sync"));
+        let shown = log.last().unwrap().shown;
+        assert!(log.toast(shown + Duration::from_secs(4)).is_some());
+        assert!(log.toast(shown + Duration::from_secs(10)).is_none());
+    }
+
+    #[test]
+    fn test_dismiss_keeps_the_message() {
+        let mut log = MessageLog::default();
+        log.push(Message::new(Severity::Error, "Failed to sync"));
+        log.dismiss();
+        assert!(log.toast(Instant::now()).is_none());
+        assert_eq!(log.last_error().unwrap().text, "Failed to sync");
+
+        log.push(Message::new(Severity::Success, "Synced ✅"));
+        assert!(log.toast(Instant::now()).is_some());
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut log = MessageLog::default();
+        log.push(Message::new(Severity::Error, "Failed\n Hint: retry"));
+        log.push(Message::new(Severity::Success, "Synced ✅"));
+        log.scroll_up(10);
+        assert_eq!(log.scroll, 10);
+        log.clamp_scroll(2);
+        assert_eq!(log.scroll, 2);
+        log.scroll_down(1);
+        assert_eq!(log.scroll, 1);
+        log.scroll_down(5);
+        assert_eq!(log.scroll, 0);
+
+        log.scroll_up(1);
+        log.push(Message::new(Severity::Success, "Copied ✅"));
+        assert_eq!(log.scroll, 0);
+    }
+}
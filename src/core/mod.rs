@@ -1,8 +1,49 @@
 pub mod app;
 pub mod app_test;
+pub mod browser;
+pub mod cache;
+pub mod cache_test;
+pub mod checks;
+pub mod checks_test;
+pub mod client;
+pub mod client_test;
+pub mod clipboard;
+pub mod compare;
+pub mod compare_test;
 pub mod config;
+pub mod config_test;
+pub mod crash;
+pub mod dashboard;
+pub mod dashboard_test;
+pub mod editor;
+pub mod editor_test;
 pub mod errors;
+pub mod errors_test;
+pub mod field;
+pub mod form;
+pub mod form_test;
 pub mod git;
 pub mod github;
+pub mod help;
+pub mod help_test;
 pub mod input_mode;
+pub mod keymap;
+pub mod keymap_test;
+pub mod message_log;
+pub mod message_log_test;
+pub mod notifications;
+pub mod notifications_test;
+pub mod palette;
+pub mod palette_test;
 pub mod pull_request;
+pub mod pull_request_test;
+pub mod screen;
+pub mod signal;
+pub mod text_input;
+pub mod text_input_test;
+pub mod theme;
+pub mod theme_test;
+pub mod time;
+pub mod time_test;
+pub mod timeline;
+pub mod timeline_test;
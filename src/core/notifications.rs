@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use octocrab::models::activity::Notification;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NotificationGroup {
+    ReviewRequested,
+    Mentioned,
+    Assigned,
+    CiActivity,
+    Other,
+}
+
+impl NotificationGroup {
+    pub fn from_reason(reason: &str) -> NotificationGroup {
+        match reason {
+            "review_requested" => NotificationGroup::ReviewRequested,
+            "mention" | "team_mention" => NotificationGroup::Mentioned,
+            "assign" => NotificationGroup::Assigned,
+            "ci_activity" => NotificationGroup::CiActivity,
+            _ => NotificationGroup::Other,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationGroup::ReviewRequested => "Review requested",
+            NotificationGroup::Mentioned => "Mentioned",
+            NotificationGroup::Assigned => "Assigned",
+            NotificationGroup::CiActivity => "CI activity",
+            NotificationGroup::Other => "Other activity",
+        }
+    }
+}
+
+/// A notification thread about a pull request, or about workflow runs, shown
+/// in the inbox.
+#[derive(Debug, Clone)]
+pub struct InboxItem {
+    pub thread_id: String,
+    pub group: NotificationGroup,
+    pub repository: String,
+    pub title: String,
+    pub pull_request: Option<u64>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl InboxItem {
+    /// Returns `None` for notifications that are not about pull requests.
+    pub fn from_notification(notification: &Notification) -> Option<InboxItem> {
+        let group = NotificationGroup::from_reason(&notification.reason);
+        let is_pull_request = notification.subject.r#type == "PullRequest";
+        if !is_pull_request && group != NotificationGroup::CiActivity {
+            return None;
+        }
+        Some(InboxItem {
+            thread_id: notification.id.to_string(),
+            group,
+            repository: notification
+                .repository
+                .full_name
+                .clone()
+                .unwrap_or_else(|| notification.repository.name.clone()),
+            title: notification.subject.title.clone(),
+            pull_request: notification
+                .subject
+                .url
+                .as_ref()
+                .filter(|_| is_pull_request)
+                .and_then(|url| pull_request_number(url.as_str())),
+            updated_at: notification.updated_at,
+        })
+    }
+
+    /// The page of the item on `web_url`, the GitHub instance it came from.
+    pub fn html_url(&self, web_url: &str) -> String {
+        match self.pull_request {
+            Some(number) => format!("{}/{}/pull/{}", web_url, self.repository, number),
+            None => format!("{}/{}/actions", web_url, self.repository),
+        }
+    }
+}
+
+/// Extracts the number from a `.../repos/{owner}/{repo}/pulls/{number}` URL.
+pub fn pull_request_number(url: &str) -> Option<u64> {
+    let (_, number) = url.trim_end_matches('/').rsplit_once("/pulls/")?;
+    number.parse().ok()
+}
+
+/// Orders items by group, most recently updated first within each group.
+pub fn sort_inbox(items: &mut [InboxItem]) {
+    items.sort_by(|a, b| {
+        a.group
+            .cmp(&b.group)
+            .then_with(|| b.updated_at.cmp(&a.updated_at))
+    });
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::notifications::{
+        pull_request_number, sort_inbox, InboxItem, NotificationGroup,
+    };
+    use octocrab::models::activity::Notification;
+    use serde_json::json;
+
+    fn notification(
+        id: &str,
+        reason: &str,
+        kind: &str,
+        url: Option<&str>,
+        updated: &str,
+    ) -> Notification {
+        serde_json::from_value(json!({
+            "id": id,
+            "repository": {
+                "id": 1,
+                "name": "prt",
+                "full_name": "djego/prt",
+                "url": "https://api.github.com/repos/djego/prt"
+            },
+            "subject": {
+                "title": format!("Notification {}", id),
+                "url": url,
+                "latest_comment_url": null,
+                "type": kind
+            },
+            "reason": reason,
+            "unread": true,
+            "updated_at": updated,
+            "last_read_at": null,
+            "url": format!("https://api.github.com/notifications/threads/{}", id)
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_notification() {
+        let item = InboxItem::from_notification(&notification(
+            "1",
+            "review_requested",
+            "PullRequest",
+            Some("https://api.github.com/repos/djego/prt/pulls/42"),
+            "2024-05-01T10:00:00Z",
+        ))
+        .unwrap();
+        assert_eq!(item.group, NotificationGroup::ReviewRequested);
+        assert_eq!(item.repository, "djego/prt");
+        assert_eq!(item.pull_request, Some(42));
+        assert_eq!(
+            item.html_url("https://github.com"),
+            "https://github.com/djego/prt/pull/42"
+        );
+
+        let ci = InboxItem::from_notification(&notification(
+            "2",
+            "ci_activity",
+            "CheckSuite",
+            None,
+            "2024-05-01T10:00:00Z",
+        ))
+        .unwrap();
+        assert_eq!(ci.group, NotificationGroup::CiActivity);
+        assert_eq!(ci.group.label(), "CI activity");
+        assert_eq!(
+            ci.html_url("https://ghe.example.com"),
+            "https://ghe.example.com/djego/prt/actions"
+        );
+        assert_eq!(ci.pull_request, None);
+
+        assert!(InboxItem::from_notification(&notification(
+            "3",
+            "subscribed",
+            "Issue",
+            Some("https://api.github.com/repos/djego/prt/issues/7"),
+            "2024-05-01T10:00:00Z",
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn test_sort_inbox() {
+        let mut items: Vec<InboxItem> = [
+            notification("1", "mention", "PullRequest", None, "2024-05-01T10:00:00Z"),
+            notification(
+                "2",
+                "review_requested",
+                "PullRequest",
+                None,
+                "2024-05-01T09:00:00Z",
+            ),
+            notification("3", "mention", "PullRequest", None, "2024-05-02T10:00:00Z"),
+        ]
+        .iter()
+        .filter_map(InboxItem::from_notification)
+        .collect();
+
+        sort_inbox(&mut items);
+
+        let ids: Vec<&str> = items.iter().map(|item| item.thread_id.as_str()).collect();
+        assert_eq!(ids, ["2", "3", "1"]);
+    }
+
+    #[test]
+    fn test_pull_request_number() {
+        assert_eq!(
+            pull_request_number("https://api.github.com/repos/djego/prt/pulls/12"),
+            Some(12)
+        );
+        assert_eq!(
+            pull_request_number("https://api.github.com/repos/djego/prt/issues/12"),
+            None
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::core::keymap::{Action, Context};
+use crate::core::text_input::TextInput;
+use crossterm::event::KeyEvent;
+use std::cmp::Reverse;
+
+/// The `Ctrl+P` palette: every action of the context it was opened in,
+/// filtered by a fuzzy query.
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    /// Where the palette was opened. Its actions run as their keys do there.
+    pub context: Context,
+    pub query: TextInput,
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    pub fn new(context: Context) -> CommandPalette {
+        CommandPalette {
+            context,
+            query: TextInput::single_line(),
+            selected: 0,
+        }
+    }
+
+    /// The actions matching the query, best match first. Ties keep the
+    /// registry order so an empty query lists the actions as declared.
+    pub fn matches(&self) -> Vec<Action> {
+        let query = self.query.text();
+        let mut matches: Vec<(u32, Action)> = Action::ALL
+            .into_iter()
+            .filter(|action| is_listed(self.context, *action))
+            .filter_map(|action| {
+                let score = fuzzy_score(&query, action.description())
+                    .max(fuzzy_score(&query, action.name()))?;
+                Some((score, action))
+            })
+            .collect();
+        matches.sort_by_key(|(score, _)| Reverse(*score));
+        matches.into_iter().map(|(_, action)| action).collect()
+    }
+
+    pub fn selected_action(&self) -> Option<Action> {
+        self.matches().get(self.selected).copied()
+    }
+
+    pub fn select_next(&mut self) {
+        let len = self.matches().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let len = self.matches().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    /// Edits the query, going back to the best match when it changes.
+    pub fn input(&mut self, key: KeyEvent) {
+        if self.query.input(key) {
+            self.selected = 0;
+        }
+    }
+
+    pub fn paste(&mut self, text: &str) {
+        if self.query.paste(text) {
+            self.selected = 0;
+        }
+    }
+}
+
+/// Actions the palette can run: those of `context`, except the ones that
+/// only open another prompt.
+fn is_listed(context: Context, action: Action) -> bool {
+    context.actions().contains(&action)
+        && !matches!(action, Action::CommandLine | Action::CommandPalette)
+}
+
+/// Scores `candidate` when every character of `query` appears in it in
+/// order, ignoring case. Consecutive characters and characters at the start
+/// of a word score higher, so `sync` ranks "Sync with GitHub" above "Show the
+/// pull request timeline".
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for c in query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let found = position + candidate[position..].iter().position(|&other| other == c)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 4;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
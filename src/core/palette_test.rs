@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::keymap::{Action, Context};
+    use crate::core::palette::{fuzzy_score, CommandPalette};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn palette(query: &str) -> CommandPalette {
+        let mut palette = CommandPalette::new(Context::Normal);
+        for c in query.chars() {
+            palette.input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        palette
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "Sync with GitHub"), Some(0));
+        assert!(fuzzy_score("sgh", "Sync with GitHub").is_some());
+        assert!(fuzzy_score("hgs", "Sync with GitHub").is_none());
+        assert!(fuzzy_score("SYNC", "Sync with GitHub").is_some());
+        assert!(
+            fuzzy_score("sync", "Sync with GitHub") > fuzzy_score("sync", "Show your new checks")
+        );
+        assert!(fuzzy_score("ob", "open browser") > fuzzy_score("ob", "job"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let all = CommandPalette::new(Context::Normal).matches();
+        assert_eq!(all.first(), Some(&Action::Quit));
+        assert!(!all.contains(&Action::CommandPalette));
+        assert!(!all.contains(&Action::CommandLine));
+        assert!(!all.contains(&Action::Submit));
+
+        assert_eq!(palette("sync").matches().first(), Some(&Action::Sync));
+        assert_eq!(
+            palette("copy repo").matches().first(),
+            Some(&Action::CopyRepositoryUrl)
+        );
+        assert_eq!(
+            palette("timeline").selected_action(),
+            Some(Action::ShowTimeline)
+        );
+        assert!(palette("zzz").matches().is_empty());
+        assert_eq!(palette("zzz").selected_action(), None);
+    }
+
+    #[test]
+    fn test_matches_follow_the_context() {
+        let timeline = CommandPalette::new(Context::Timeline).matches();
+        assert_eq!(timeline.first(), Some(&Action::Quit));
+        assert!(timeline.contains(&Action::ResolveThread));
+        assert!(timeline.contains(&Action::ReactRocket));
+        assert!(!timeline.contains(&Action::NewPullRequest));
+        assert!(!timeline.contains(&Action::CommandPalette));
+
+        let inbox = CommandPalette::new(Context::Inbox).matches();
+        assert!(inbox.contains(&Action::MarkRead));
+        assert!(!inbox.contains(&Action::ShowTimeline));
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut palette = palette("open");
+        let matches = palette.matches();
+        assert!(matches.len() > 1);
+        palette.select_next();
+        assert_eq!(palette.selected_action(), Some(matches[1]));
+        palette.select_previous();
+        palette.select_previous();
+        assert_eq!(palette.selected_action(), matches.last().copied());
+
+        palette.input(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(palette.selected, 0);
+    }
+}
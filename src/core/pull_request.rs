@@ -1,17 +1,178 @@
+use crate::core::checks::Check;
+use octocrab::models::pulls::{PullRequest as OctocrabPullRequest, ReviewState};
+use octocrab::models::{IssueState, StatusState};
+use serde::Serialize;
+
+#[derive(Clone)]
 pub struct PullRequest {
     pub title: String,
     pub description: String,
     pub source_branch: String,
     pub target_branch: String,
+    pub draft: bool,
+}
+
+/// Stable, serializable view of a GitHub pull request used for CLI output.
+#[derive(Debug, Serialize)]
+pub struct PullRequestSummary {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub draft: bool,
+    pub author: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mergeable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<Check>,
+}
+
+impl From<&OctocrabPullRequest> for PullRequestSummary {
+    fn from(pr: &OctocrabPullRequest) -> PullRequestSummary {
+        let state = if pr.merged_at.is_some() {
+            "merged"
+        } else {
+            match pr.state {
+                Some(IssueState::Closed) => "closed",
+                _ => "open",
+            }
+        };
+        PullRequestSummary {
+            number: pr.number,
+            title: pr.title.clone().unwrap_or_default(),
+            state: state.to_string(),
+            draft: pr.draft.unwrap_or(false),
+            author: pr
+                .user
+                .as_ref()
+                .map(|user| user.login.clone())
+                .unwrap_or_default(),
+            source_branch: pr.head.ref_field.clone(),
+            target_branch: pr.base.ref_field.clone(),
+            url: pr
+                .html_url
+                .as_ref()
+                .map(|url| url.to_string())
+                .unwrap_or_default(),
+            mergeable: pr.mergeable,
+            body: pr.body.clone(),
+            checks: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksState {
+    None,
+    Pending,
+    Success,
+    Failure,
+}
+
+impl ChecksState {
+    /// Combines legacy commit statuses and check run conclusions the way the
+    /// GitHub UI does: any failure wins, then anything still running.
+    pub fn from_results(statuses: &[StatusState], conclusions: &[Option<String>]) -> ChecksState {
+        if statuses.is_empty() && conclusions.is_empty() {
+            return ChecksState::None;
+        }
+        let failed = statuses
+            .iter()
+            .any(|state| matches!(state, StatusState::Failure | StatusState::Error))
+            || conclusions.iter().flatten().any(|conclusion| {
+                matches!(
+                    conclusion.as_str(),
+                    "failure" | "timed_out" | "cancelled" | "action_required" | "startup_failure"
+                )
+            });
+        let pending = statuses
+            .iter()
+            .any(|state| matches!(state, StatusState::Pending))
+            || conclusions.iter().any(|conclusion| conclusion.is_none());
+
+        if failed {
+            ChecksState::Failure
+        } else if pending {
+            ChecksState::Pending
+        } else {
+            ChecksState::Success
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChecksState::None => "no checks",
+            ChecksState::Pending => "● pending",
+            ChecksState::Success => "✔ passing",
+            ChecksState::Failure => "✘ failing",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewDecision {
+    None,
+    ReviewRequired,
+    Approved,
+    ChangesRequested,
 }
 
-impl PullRequest {
-    pub fn new(current_branch: String, target_branch: String) -> PullRequest {
-        PullRequest {
-            title: String::new(),
-            description: String::new(),
-            source_branch: current_branch,
-            target_branch,
+impl ReviewDecision {
+    /// Only the latest approving or blocking review of each reviewer counts;
+    /// `reviews` must be in submission order.
+    pub fn from_reviews(
+        reviews: &[(String, ReviewState)],
+        review_requested: bool,
+    ) -> ReviewDecision {
+        let mut latest: Vec<(&str, &ReviewState)> = Vec::new();
+        for (login, state) in reviews {
+            if !matches!(
+                state,
+                ReviewState::Approved | ReviewState::ChangesRequested | ReviewState::Dismissed
+            ) {
+                continue;
+            }
+            match latest.iter_mut().find(|(user, _)| user == login) {
+                Some(entry) => entry.1 = state,
+                None => latest.push((login, state)),
+            }
+        }
+
+        if latest
+            .iter()
+            .any(|(_, state)| **state == ReviewState::ChangesRequested)
+        {
+            ReviewDecision::ChangesRequested
+        } else if latest
+            .iter()
+            .any(|(_, state)| **state == ReviewState::Approved)
+        {
+            ReviewDecision::Approved
+        } else if review_requested {
+            ReviewDecision::ReviewRequired
+        } else {
+            ReviewDecision::None
         }
     }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewDecision::None => "no reviews",
+            ReviewDecision::ReviewRequired => "review required",
+            ReviewDecision::Approved => "approved",
+            ReviewDecision::ChangesRequested => "changes requested",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PullRequestStatus {
+    pub checks: ChecksState,
+    pub review: ReviewDecision,
+    /// The individual statuses and check runs `checks` is derived from.
+    pub runs: Vec<Check>,
 }
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::pull_request::{ChecksState, ReviewDecision};
+    use octocrab::models::pulls::ReviewState;
+    use octocrab::models::StatusState;
+
+    #[test]
+    fn test_checks_state_from_results() {
+        assert_eq!(ChecksState::from_results(&[], &[]), ChecksState::None);
+        assert_eq!(
+            ChecksState::from_results(
+                &[StatusState::Success],
+                &[Some("success".to_string()), Some("skipped".to_string())]
+            ),
+            ChecksState::Success
+        );
+        assert_eq!(
+            ChecksState::from_results(&[StatusState::Success], &[None]),
+            ChecksState::Pending
+        );
+        assert_eq!(
+            ChecksState::from_results(&[StatusState::Pending], &[Some("failure".to_string())]),
+            ChecksState::Failure
+        );
+        assert_eq!(
+            ChecksState::from_results(&[StatusState::Error], &[]),
+            ChecksState::Failure
+        );
+    }
+
+    #[test]
+    fn test_review_decision_uses_latest_review_per_user() {
+        let reviews = vec![
+            ("alice".to_string(), ReviewState::ChangesRequested),
+            ("bob".to_string(), ReviewState::Commented),
+            ("alice".to_string(), ReviewState::Approved),
+        ];
+        assert_eq!(
+            ReviewDecision::from_reviews(&reviews, false),
+            ReviewDecision::Approved
+        );
+
+        let reviews = vec![
+            ("alice".to_string(), ReviewState::Approved),
+            ("bob".to_string(), ReviewState::ChangesRequested),
+        ];
+        assert_eq!(
+            ReviewDecision::from_reviews(&reviews, false),
+            ReviewDecision::ChangesRequested
+        );
+
+        let reviews = vec![
+            ("alice".to_string(), ReviewState::Approved),
+            ("alice".to_string(), ReviewState::Dismissed),
+        ];
+        assert_eq!(
+            ReviewDecision::from_reviews(&reviews, true),
+            ReviewDecision::ReviewRequired
+        );
+        assert_eq!(
+            ReviewDecision::from_reviews(&[], false),
+            ReviewDecision::None
+        );
+    }
+}
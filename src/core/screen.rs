@@ -0,0 +1,9 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Main,
+    Notifications,
+    Dashboard,
+    Timeline,
+    /// The message log, full screen.
+    Messages,
+}
@@ -0,0 +1,32 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Spawns a listener on `runtime` that raises the returned flag when the
+/// process receives SIGTERM or SIGHUP, so the event loop can exit cleanly.
+pub fn listen_for_shutdown(runtime: &Runtime) -> Arc<AtomicBool> {
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let flag = shutdown.clone();
+
+    #[cfg(unix)]
+    runtime.spawn(async move {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let (Ok(mut terminate), Ok(mut hangup)) = (
+            signal(SignalKind::terminate()),
+            signal(SignalKind::hangup()),
+        ) else {
+            return;
+        };
+        tokio::select! {
+            _ = terminate.recv() => {}
+            _ = hangup.recv() => {}
+        }
+        flag.store(true, Ordering::SeqCst);
+    });
+
+    #[cfg(not(unix))]
+    let _ = (runtime, flag);
+
+    shutdown
+}
@@ -0,0 +1,91 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tui_textarea::{CursorMove, TextArea};
+
+/// A line editor for one form field, backed by a `TextArea` so every field
+/// gets cursor movement, Home/End, Delete, word deletion and undo. A single
+/// line input never contains a newline, whether typed or pasted.
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    area: TextArea<'static>,
+    multiline: bool,
+}
+
+impl TextInput {
+    pub fn single_line() -> TextInput {
+        TextInput {
+            area: TextArea::default(),
+            multiline: false,
+        }
+    }
+
+    pub fn multiline() -> TextInput {
+        TextInput {
+            area: TextArea::default(),
+            multiline: true,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.area.lines().join("\n")
+    }
+
+    pub fn text_area(&self) -> &TextArea<'static> {
+        &self.area
+    }
+
+    /// Replaces the contents, leaving the cursor at the end. Does nothing
+    /// when the text is unchanged, so the cursor and undo history survive.
+    pub fn set_text(&mut self, text: &str) {
+        if self.text() == text {
+            return;
+        }
+        let text = self.normalize(text);
+        self.area = TextArea::from(text.split('\n'));
+        self.area.move_cursor(CursorMove::Bottom);
+        self.area.move_cursor(CursorMove::End);
+    }
+
+    /// Applies a key press and returns whether the text changed.
+    pub fn input(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('\n' | '\r') if !self.multiline => false,
+            KeyCode::Char('m') if ctrl && !self.multiline => false,
+            KeyCode::Backspace if ctrl => self.area.delete_word(),
+            KeyCode::Delete if ctrl => self.area.delete_next_word(),
+            _ => self.area.input(key),
+        }
+    }
+
+    /// Moves the cursor `lines` down, or up when negative, which scrolls the
+    /// view along with it.
+    pub fn scroll(&mut self, lines: i16) {
+        let step = if lines < 0 {
+            CursorMove::Up
+        } else {
+            CursorMove::Down
+        };
+        for _ in 0..lines.unsigned_abs() {
+            self.area.move_cursor(step);
+        }
+    }
+
+    /// Inserts pasted text at the cursor. Line breaks become spaces in a
+    /// single line input.
+    pub fn paste(&mut self, text: &str) -> bool {
+        let text = self.normalize(text);
+        self.area.insert_str(text)
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        if self.multiline {
+            text
+        } else {
+            text.split('\n')
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+}
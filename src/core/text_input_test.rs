@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::text_input::TextInput;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::CONTROL)
+    }
+
+    fn type_text(input: &mut TextInput, text: &str) {
+        for c in text.chars() {
+            input.input(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_cursor_editing() {
+        let mut input = TextInput::single_line();
+        type_text(&mut input, "feature");
+        input.input(key(KeyCode::Home));
+        type_text(&mut input, "my-");
+        input.input(key(KeyCode::End));
+        input.input(key(KeyCode::Left));
+        input.input(key(KeyCode::Delete));
+        assert_eq!(input.text(), "my-featur");
+
+        input.input(key(KeyCode::Home));
+        input.input(key(KeyCode::Delete));
+        assert_eq!(input.text(), "y-featur");
+    }
+
+    #[test]
+    fn test_backspace_removes_whole_characters() {
+        let mut input = TextInput::single_line();
+        type_text(&mut input, "café ✅");
+        input.input(key(KeyCode::Backspace));
+        input.input(key(KeyCode::Backspace));
+        assert_eq!(input.text(), "café");
+        input.input(key(KeyCode::Backspace));
+        assert_eq!(input.text(), "caf");
+    }
+
+    #[test]
+    fn test_word_deletion() {
+        let mut input = TextInput::single_line();
+        type_text(&mut input, "Fix the parser");
+        input.input(ctrl(KeyCode::Char('w')));
+        assert_eq!(input.text(), "Fix the ");
+        input.input(ctrl(KeyCode::Backspace));
+        assert_eq!(input.text(), "Fix ");
+
+        input.input(key(KeyCode::Home));
+        input.input(ctrl(KeyCode::Delete));
+        assert_eq!(input.text(), " ");
+    }
+
+    #[test]
+    fn test_single_line_rejects_newlines() {
+        let mut input = TextInput::single_line();
+        type_text(&mut input, "title");
+        assert!(!input.input(key(KeyCode::Enter)));
+        assert!(!input.input(ctrl(KeyCode::Char('m'))));
+        input.paste("\nfirst\r\nsecond\n");
+        assert_eq!(input.text(), "titlefirst second");
+
+        input.set_text("one\ntwo");
+        assert_eq!(input.text(), "one two");
+    }
+
+    #[test]
+    fn test_multiline() {
+        let mut input = TextInput::multiline();
+        type_text(&mut input, "one");
+        assert!(input.input(key(KeyCode::Enter)));
+        input.paste("two\r\nthree");
+        assert_eq!(input.text(), "one\ntwo\nthree");
+
+        input.set_text("replaced\ntext");
+        type_text(&mut input, "!");
+        assert_eq!(input.text(), "replaced\ntext!");
+    }
+}
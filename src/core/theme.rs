@@ -0,0 +1,210 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::str::FromStr;
+
+/// The `[theme]` section of the config: a built-in theme and colours for
+/// some of its styles, such as `error = "#ff5555"` or
+/// `focused = "black on yellow"`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ThemeConfig {
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub colors: BTreeMap<String, String>,
+}
+
+impl ThemeConfig {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.colors.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeName {
+    Dark,
+    Light,
+    HighContrast,
+    /// Attributes such as bold and reversed instead of colours, for
+    /// `NO_COLOR`.
+    Monochrome,
+}
+
+impl ThemeName {
+    pub fn from_name(name: &str) -> Option<ThemeName> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "dark" => Some(ThemeName::Dark),
+            "light" => Some(ThemeName::Light),
+            "high-contrast" => Some(ThemeName::HighContrast),
+            "monochrome" | "none" | "no-color" => Some(ThemeName::Monochrome),
+            _ => None,
+        }
+    }
+}
+
+/// The named styles the UI is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// The selected field or list item.
+    pub focused: Style,
+    /// The field being typed into.
+    pub editing: Style,
+    pub error: Style,
+    pub success: Style,
+    /// Pending checks, pending reviews and other things to keep an eye on.
+    pub warning: Style,
+    /// Secondary text such as log lines and action names.
+    pub muted: Style,
+    /// Keys, links and references.
+    pub accent: Style,
+    pub border: Style,
+    /// Code in comments and descriptions.
+    pub code: Style,
+    /// Keywords in highlighted code blocks.
+    pub keyword: Style,
+    /// Whether anything may be coloured. Without it the frame is drawn in the
+    /// terminal's own colours, including the markdown preview.
+    pub color: bool,
+}
+
+impl Theme {
+    pub fn named(name: ThemeName) -> Theme {
+        let fg = |color| Style::default().fg(color);
+        match name {
+            ThemeName::Dark => Theme {
+                focused: fg(Color::Yellow),
+                editing: fg(Color::Green),
+                error: fg(Color::Red),
+                success: fg(Color::Green),
+                warning: fg(Color::Yellow),
+                muted: fg(Color::DarkGray),
+                accent: fg(Color::Cyan),
+                border: Style::default(),
+                code: fg(Color::Cyan),
+                keyword: fg(Color::Magenta).add_modifier(Modifier::BOLD),
+                color: true,
+            },
+            ThemeName::Light => Theme {
+                focused: fg(Color::Blue),
+                editing: fg(Color::Magenta),
+                error: fg(Color::Red),
+                success: fg(Color::Green),
+                warning: fg(Color::Rgb(0xb0, 0x70, 0x00)),
+                muted: fg(Color::DarkGray),
+                accent: fg(Color::Blue),
+                border: fg(Color::Gray),
+                code: fg(Color::Rgb(0x00, 0x6e, 0x7a)),
+                keyword: fg(Color::Magenta).add_modifier(Modifier::BOLD),
+                color: true,
+            },
+            ThemeName::HighContrast => Theme {
+                focused: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+                editing: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+                error: fg(Color::LightRed).add_modifier(Modifier::BOLD),
+                success: fg(Color::LightGreen).add_modifier(Modifier::BOLD),
+                warning: fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+                muted: fg(Color::White),
+                accent: fg(Color::LightCyan).add_modifier(Modifier::BOLD),
+                border: fg(Color::White),
+                code: fg(Color::LightCyan),
+                keyword: fg(Color::LightMagenta).add_modifier(Modifier::BOLD),
+                color: true,
+            },
+            ThemeName::Monochrome => Theme {
+                focused: Style::default().add_modifier(Modifier::BOLD),
+                editing: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                error: Style::default().add_modifier(Modifier::BOLD),
+                success: Style::default().add_modifier(Modifier::BOLD),
+                warning: Style::default(),
+                muted: Style::default(),
+                accent: Style::default().add_modifier(Modifier::UNDERLINED),
+                border: Style::default(),
+                code: Style::default(),
+                keyword: Style::default().add_modifier(Modifier::BOLD),
+                color: false,
+            },
+        }
+    }
+
+    /// Builds the theme described by the config, with a warning for every
+    /// unknown theme, style or colour. `NO_COLOR` picks the monochrome theme
+    /// when the config doesn't choose one, as the convention asks, and then
+    /// the colour overrides are only checked, not applied.
+    pub fn from_config(config: &ThemeConfig, no_color: bool) -> (Theme, Vec<String>) {
+        let mut warnings = Vec::new();
+        let name = match config.name.as_deref() {
+            Some(name) => ThemeName::from_name(name).unwrap_or_else(|| {
+                warnings.push(format!(
+                    "Unknown theme \"{}\", use dark, light, high-contrast or monochrome",
+                    name
+                ));
+                ThemeName::Dark
+            }),
+            None if no_color => ThemeName::Monochrome,
+            None => ThemeName::Dark,
+        };
+        let mut theme = Theme::named(name);
+        let apply_colors = !(no_color && config.name.is_none());
+
+        for (style_name, value) in &config.colors {
+            let Some(style) = theme.style_mut(style_name) else {
+                warnings.push(format!("Unknown style \"{}\" in the theme", style_name));
+                continue;
+            };
+            match parse_style(value) {
+                Ok(_) if !apply_colors => {}
+                Ok(parsed) => {
+                    *style = parsed;
+                    theme.color = true;
+                }
+                Err(e) => warnings.push(e),
+            }
+        }
+        (theme, warnings)
+    }
+
+    fn style_mut(&mut self, name: &str) -> Option<&mut Style> {
+        match name {
+            "focused" => Some(&mut self.focused),
+            "editing" => Some(&mut self.editing),
+            "error" => Some(&mut self.error),
+            "success" => Some(&mut self.success),
+            "warning" => Some(&mut self.warning),
+            "muted" => Some(&mut self.muted),
+            "accent" => Some(&mut self.accent),
+            "border" => Some(&mut self.border),
+            "code" => Some(&mut self.code),
+            "keyword" => Some(&mut self.keyword),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::named(ThemeName::Dark)
+    }
+}
+
+/// Whether `NO_COLOR` is set to anything but an empty string.
+pub fn no_color() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// `red`, `#ff5555`, `208` or `black on yellow`.
+fn parse_style(value: &str) -> Result<Style, String> {
+    let color = |text: &str| {
+        Color::from_str(text.trim())
+            .map_err(|_| format!("Unknown colour \"{}\" in the theme", text.trim()))
+    };
+    match value.split_once(" on ") {
+        Some((fg, bg)) => Ok(Style::default().fg(color(fg)?).bg(color(bg)?)),
+        None => Ok(Style::default().fg(color(value)?)),
+    }
+}
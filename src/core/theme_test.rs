@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::theme::{Theme, ThemeConfig, ThemeName};
+    use ratatui::style::{Color, Modifier, Style};
+
+    fn config(name: Option<&str>, colors: &[(&str, &str)]) -> ThemeConfig {
+        ThemeConfig {
+            name: name.map(str::to_string),
+            colors: colors
+                .iter()
+                .map(|(style, color)| (style.to_string(), color.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_theme_names() {
+        assert_eq!(ThemeName::from_name("Light"), Some(ThemeName::Light));
+        assert_eq!(
+            ThemeName::from_name("high_contrast"),
+            Some(ThemeName::HighContrast)
+        );
+        assert_eq!(ThemeName::from_name("none"), Some(ThemeName::Monochrome));
+        assert_eq!(ThemeName::from_name("solarized"), None);
+
+        let dark = Theme::default();
+        assert_eq!(dark.error.fg, Some(Color::Red));
+        assert_eq!(dark.success.fg, Some(Color::Green));
+        assert_ne!(dark, Theme::named(ThemeName::Light));
+
+        let monochrome = Theme::named(ThemeName::Monochrome);
+        assert!(!monochrome.color);
+        assert!(monochrome.focused.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(monochrome.error.fg, None);
+        assert!(monochrome.success.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_no_color() {
+        let (theme, warnings) = Theme::from_config(&ThemeConfig::default(), true);
+        assert!(warnings.is_empty());
+        assert_eq!(theme, Theme::named(ThemeName::Monochrome));
+
+        // A theme chosen in the config wins over NO_COLOR.
+        let (theme, _) = Theme::from_config(&config(Some("light"), &[]), true);
+        assert_eq!(theme, Theme::named(ThemeName::Light));
+
+        // Colour overrides don't bring the colours back, but are still checked.
+        let (theme, warnings) =
+            Theme::from_config(&config(None, &[("error", "red"), ("code", "teal")]), true);
+        assert_eq!(theme, Theme::named(ThemeName::Monochrome));
+        assert_eq!(warnings, ["Unknown colour \"teal\" in the theme"]);
+
+        let (theme, _) = Theme::from_config(&ThemeConfig::default(), false);
+        assert_eq!(theme, Theme::default());
+    }
+
+    #[test]
+    fn test_color_overrides() {
+        let (theme, warnings) = Theme::from_config(
+            &config(
+                Some("high-contrast"),
+                &[
+                    ("error", "#ff5555"),
+                    ("focused", "black on yellow"),
+                    ("border", "8"),
+                ],
+            ),
+            false,
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(
+            theme.error,
+            Style::default().fg(Color::Rgb(0xff, 0x55, 0x55))
+        );
+        assert_eq!(
+            theme.focused,
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        );
+        assert_eq!(theme.border, Style::default().fg(Color::Indexed(8)));
+        assert_eq!(theme.success, Theme::named(ThemeName::HighContrast).success);
+    }
+
+    #[test]
+    fn test_theme_warnings() {
+        let (theme, warnings) = Theme::from_config(
+            &config(Some("solarized"), &[("title", "red"), ("error", "reddish")]),
+            false,
+        );
+        assert_eq!(
+            warnings,
+            [
+                "Unknown theme \"solarized\", use dark, light, high-contrast or monochrome",
+                "Unknown colour \"reddish\" in the theme",
+                "Unknown style \"title\" in the theme",
+            ]
+        );
+        assert_eq!(theme, Theme::default());
+    }
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+
+/// Short age of `then` as seen at `now`, such as "5m ago" or "3d ago".
+pub fn relative_time(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - then).num_seconds().max(0);
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        86400..=2591999 => format!("{}d ago", seconds / 86400),
+        2592000..=31535999 => format!("{}mo ago", seconds / 2592000),
+        _ => format!("{}y ago", seconds / 31536000),
+    }
+}
@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::time::relative_time;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_relative_time() {
+        let now = Utc::now();
+        assert_eq!(relative_time(now, now), "just now");
+        assert_eq!(relative_time(now + Duration::seconds(30), now), "just now");
+        assert_eq!(relative_time(now - Duration::minutes(5), now), "5m ago");
+        assert_eq!(relative_time(now - Duration::hours(3), now), "3h ago");
+        assert_eq!(relative_time(now - Duration::days(2), now), "2d ago");
+        assert_eq!(relative_time(now - Duration::days(65), now), "2mo ago");
+        assert_eq!(relative_time(now - Duration::days(800), now), "2y ago");
+    }
+}
@@ -0,0 +1,377 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Reactions GitHub supports, in the order of the keys `1`..`8`.
+pub const REACTIONS: [&str; 8] = [
+    "+1", "-1", "laugh", "confused", "heart", "hooray", "rocket", "eyes",
+];
+
+pub fn reaction_emoji(content: &str) -> &'static str {
+    match content {
+        "+1" => "👍",
+        "-1" => "👎",
+        "laugh" => "😄",
+        "confused" => "😕",
+        "heart" => "❤️",
+        "hooray" => "🎉",
+        "rocket" => "🚀",
+        "eyes" => "👀",
+        _ => "?",
+    }
+}
+
+/// A review thread as known to the GraphQL API, which is the only way to
+/// resolve one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewThread {
+    pub id: String,
+    pub resolved: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineKind {
+    Comment,
+    Review {
+        state: String,
+    },
+    ReviewComment {
+        path: String,
+        line: Option<u64>,
+        reply: bool,
+        root_id: u64,
+        thread: Option<ReviewThread>,
+    },
+    Commit {
+        sha: String,
+    },
+    Event,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry {
+    /// Comment or review id, used to react and reply.
+    pub id: Option<u64>,
+    pub author: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub kind: TimelineKind,
+    /// Markdown body of comments and reviews, or the description of an event.
+    pub body: String,
+    pub reactions: Vec<(String, u64)>,
+}
+
+impl TimelineEntry {
+    /// Parses an entry of the issue timeline API. Review comments are left
+    /// out, they are fetched separately to keep their threads.
+    pub fn from_timeline_event(event: &Value) -> Option<TimelineEntry> {
+        let kind = str_field(event, "event")?;
+        let actor = login(event, "actor");
+        let created_at = date(event, "created_at");
+        let entry = |kind: TimelineKind, body: String| TimelineEntry {
+            id: None,
+            author: actor.clone(),
+            created_at,
+            kind,
+            body,
+            reactions: Vec::new(),
+        };
+
+        let entry = match kind {
+            "commented" => TimelineEntry {
+                id: event.get("id").and_then(Value::as_u64),
+                author: login(event, "user"),
+                body: str_field(event, "body").unwrap_or_default().to_string(),
+                reactions: reactions(event),
+                ..entry(TimelineKind::Comment, String::new())
+            },
+            "reviewed" => TimelineEntry {
+                id: event.get("id").and_then(Value::as_u64),
+                author: login(event, "user"),
+                created_at: date(event, "submitted_at"),
+                body: str_field(event, "body").unwrap_or_default().to_string(),
+                ..entry(
+                    TimelineKind::Review {
+                        state: str_field(event, "state").unwrap_or_default().to_lowercase(),
+                    },
+                    String::new(),
+                )
+            },
+            "committed" => {
+                let sha = str_field(event, "sha").unwrap_or_default().to_string();
+                let message = str_field(event, "message").unwrap_or_default();
+                TimelineEntry {
+                    author: event
+                        .pointer("/author/name")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    created_at: event
+                        .pointer("/author/date")
+                        .and_then(Value::as_str)
+                        .and_then(|date| date.parse().ok()),
+                    ..entry(
+                        TimelineKind::Commit { sha },
+                        message.lines().next().unwrap_or_default().to_string(),
+                    )
+                }
+            }
+            "labeled" | "unlabeled" => entry(
+                TimelineKind::Event,
+                format!(
+                    "{} the {} label",
+                    if kind == "labeled" {
+                        "added"
+                    } else {
+                        "removed"
+                    },
+                    event
+                        .pointer("/label/name")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                ),
+            ),
+            "head_ref_force_pushed" => entry(
+                TimelineKind::Event,
+                "force-pushed the head branch".to_string(),
+            ),
+            "merged" => entry(
+                TimelineKind::Event,
+                format!(
+                    "merged commit {}",
+                    short_sha(str_field(event, "commit_id").unwrap_or_default())
+                ),
+            ),
+            "closed" => entry(TimelineKind::Event, "closed this".to_string()),
+            "reopened" => entry(TimelineKind::Event, "reopened this".to_string()),
+            "ready_for_review" => entry(
+                TimelineKind::Event,
+                "marked this ready for review".to_string(),
+            ),
+            "convert_to_draft" => {
+                entry(TimelineKind::Event, "converted this to a draft".to_string())
+            }
+            "review_requested" => entry(
+                TimelineKind::Event,
+                format!(
+                    "requested a review from @{}",
+                    event
+                        .pointer("/requested_reviewer/login")
+                        .or_else(|| event.pointer("/requested_team/slug"))
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                ),
+            ),
+            "assigned" => entry(
+                TimelineKind::Event,
+                format!("assigned @{}", login(event, "assignee")),
+            ),
+            "renamed" => entry(
+                TimelineKind::Event,
+                format!(
+                    "renamed this from \"{}\" to \"{}\"",
+                    event
+                        .pointer("/rename/from")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default(),
+                    event
+                        .pointer("/rename/to")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                ),
+            ),
+            "head_ref_deleted" => entry(TimelineKind::Event, "deleted the head branch".to_string()),
+            _ => return None,
+        };
+        Some(entry)
+    }
+
+    /// Parses a pull request review comment. `threads` maps the id of the
+    /// first comment of each thread to the thread.
+    pub fn from_review_comment(
+        comment: &Value,
+        threads: &HashMap<u64, ReviewThread>,
+    ) -> Option<TimelineEntry> {
+        let id = comment.get("id").and_then(Value::as_u64)?;
+        let reply_to = comment.get("in_reply_to_id").and_then(Value::as_u64);
+        let root_id = reply_to.unwrap_or(id);
+        Some(TimelineEntry {
+            id: Some(id),
+            author: login(comment, "user"),
+            created_at: date(comment, "created_at"),
+            kind: TimelineKind::ReviewComment {
+                path: str_field(comment, "path").unwrap_or_default().to_string(),
+                line: comment
+                    .get("line")
+                    .and_then(Value::as_u64)
+                    .or_else(|| comment.get("original_line").and_then(Value::as_u64)),
+                reply: reply_to.is_some(),
+                root_id,
+                thread: threads.get(&root_id).cloned(),
+            },
+            body: str_field(comment, "body").unwrap_or_default().to_string(),
+            reactions: reactions(comment),
+        })
+    }
+
+    pub fn is_comment(&self) -> bool {
+        matches!(
+            self.kind,
+            TimelineKind::Comment | TimelineKind::ReviewComment { .. }
+        )
+    }
+
+    pub fn thread(&self) -> Option<&ReviewThread> {
+        match self.kind {
+            TimelineKind::ReviewComment { ref thread, .. } => thread.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// What the author did, shown before the timestamp.
+    pub fn action(&self) -> String {
+        match self.kind {
+            TimelineKind::Comment => "commented".to_string(),
+            TimelineKind::Review { ref state } => match state.as_str() {
+                "approved" => "approved these changes".to_string(),
+                "changes_requested" => "requested changes".to_string(),
+                "dismissed" => "had their review dismissed".to_string(),
+                _ => "reviewed".to_string(),
+            },
+            TimelineKind::ReviewComment {
+                ref path,
+                line,
+                reply,
+                ..
+            } => {
+                let location = match line {
+                    Some(line) => format!("{}:{}", path, line),
+                    None => path.clone(),
+                };
+                if reply {
+                    "replied".to_string()
+                } else {
+                    format!("commented on {}", location)
+                }
+            }
+            TimelineKind::Commit { ref sha } => format!("committed {}", short_sha(sha)),
+            TimelineKind::Event => self.body.clone(),
+        }
+    }
+}
+
+/// Orders issue timeline entries and review comments by time, keeping the
+/// replies of each review thread right after its first comment.
+pub fn build_timeline(
+    mut entries: Vec<TimelineEntry>,
+    review_comments: Vec<TimelineEntry>,
+) -> Vec<TimelineEntry> {
+    let mut threads: Vec<Vec<TimelineEntry>> = Vec::new();
+    for comment in review_comments {
+        let TimelineKind::ReviewComment { root_id, .. } = comment.kind else {
+            continue;
+        };
+        match threads
+            .iter_mut()
+            .find(|thread| thread[0].id == Some(root_id))
+        {
+            Some(thread) => thread.push(comment),
+            None => threads.push(vec![comment]),
+        }
+    }
+
+    let mut blocks: Vec<Vec<TimelineEntry>> = entries.drain(..).map(|entry| vec![entry]).collect();
+    blocks.extend(threads);
+    blocks.sort_by_key(|block| block[0].created_at);
+    blocks.into_iter().flatten().collect()
+}
+
+/// Review threads from the GraphQL `reviewThreads` connection, keyed by
+/// the database id of their first comment.
+pub fn review_threads(response: &Value) -> HashMap<u64, ReviewThread> {
+    response
+        .pointer("/data/repository/pullRequest/reviewThreads/nodes")
+        .and_then(Value::as_array)
+        .map(|nodes| {
+            nodes
+                .iter()
+                .filter_map(|node| {
+                    let root = node
+                        .pointer("/comments/nodes/0/databaseId")
+                        .and_then(Value::as_u64)?;
+                    let thread = ReviewThread {
+                        id: str_field(node, "id")?.to_string(),
+                        resolved: node
+                            .get("isResolved")
+                            .and_then(Value::as_bool)
+                            .unwrap_or(false),
+                    };
+                    Some((root, thread))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The cursor of the next page of review threads, if the response says
+/// there is one.
+pub fn next_threads_cursor(response: &Value) -> Option<String> {
+    let page = response.pointer("/data/repository/pullRequest/reviewThreads/pageInfo")?;
+    if !page.get("hasNextPage").and_then(Value::as_bool)? {
+        return None;
+    }
+    str_field(page, "endCursor").map(str::to_string)
+}
+
+fn reactions(value: &Value) -> Vec<(String, u64)> {
+    let Some(reactions) = value.get("reactions") else {
+        return Vec::new();
+    };
+    REACTIONS
+        .iter()
+        .filter_map(|content| {
+            let count = reactions.get(*content).and_then(Value::as_u64)?;
+            (count > 0).then(|| (content.to_string(), count))
+        })
+        .collect()
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+fn login(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(|user| user.get("login"))
+        .and_then(Value::as_str)
+        .unwrap_or("ghost")
+        .to_string()
+}
+
+fn date(value: &Value, key: &str) -> Option<DateTime<Utc>> {
+    str_field(value, key)?.parse().ok()
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+pub const REVIEW_THREADS_QUERY: &str =
+    "query($owner: String!, $name: String!, $number: Int!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $after) {
+        pageInfo { hasNextPage endCursor }
+        nodes { id isResolved comments(first: 1) { nodes { databaseId } } }
+      }
+    }
+  }
+}";
+
+pub const RESOLVE_THREAD_MUTATION: &str = "mutation($id: ID!) {
+  resolveReviewThread(input: { threadId: $id }) { thread { isResolved } }
+}";
+
+pub const UNRESOLVE_THREAD_MUTATION: &str = "mutation($id: ID!) {
+  unresolveReviewThread(input: { threadId: $id }) { thread { isResolved } }
+}";
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::timeline::{
+        build_timeline, next_threads_cursor, review_threads, ReviewThread, TimelineEntry,
+        TimelineKind,
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_timeline_event() {
+        let comment = TimelineEntry::from_timeline_event(&json!({
+            "event": "commented",
+            "id": 10,
+            "user": { "login": "alice" },
+            "created_at": "2024-05-01T10:00:00Z",
+            "body": "Looks good",
+            "reactions": { "total_count": 2, "+1": 2, "heart": 0 }
+        }))
+        .unwrap();
+        assert_eq!(comment.id, Some(10));
+        assert_eq!(comment.author, "alice");
+        assert_eq!(comment.action(), "commented");
+        assert_eq!(comment.reactions, [("+1".to_string(), 2)]);
+
+        let review = TimelineEntry::from_timeline_event(&json!({
+            "event": "reviewed",
+            "id": 11,
+            "user": { "login": "bob" },
+            "submitted_at": "2024-05-01T11:00:00Z",
+            "state": "APPROVED",
+            "body": ""
+        }))
+        .unwrap();
+        assert_eq!(review.action(), "approved these changes");
+        assert!(review.created_at.is_some());
+
+        let commit = TimelineEntry::from_timeline_event(&json!({
+            "event": "committed",
+            "sha": "0123456789abcdef",
+            "message": "Fix bug\n\nLonger explanation",
+            "author": { "name": "Alice", "date": "2024-05-01T09:00:00Z" }
+        }))
+        .unwrap();
+        assert_eq!(commit.action(), "committed 0123456");
+        assert_eq!(commit.body, "Fix bug");
+
+        let label = TimelineEntry::from_timeline_event(&json!({
+            "event": "labeled",
+            "actor": { "login": "carol" },
+            "created_at": "2024-05-01T12:00:00Z",
+            "label": { "name": "bug" }
+        }))
+        .unwrap();
+        assert_eq!(label.kind, TimelineKind::Event);
+        assert_eq!(label.action(), "added the bug label");
+
+        assert!(TimelineEntry::from_timeline_event(&json!({ "event": "subscribed" })).is_none());
+    }
+
+    #[test]
+    fn test_build_timeline_keeps_threads_together() {
+        let threads = HashMap::from([(
+            20,
+            ReviewThread {
+                id: "PRRT_1".to_string(),
+                resolved: true,
+            },
+        )]);
+        let review_comment = |id: u64, reply_to: Option<u64>, created_at: &str| {
+            TimelineEntry::from_review_comment(
+                &json!({
+                    "id": id,
+                    "in_reply_to_id": reply_to,
+                    "user": { "login": "alice" },
+                    "created_at": created_at,
+                    "path": "src/main.rs",
+                    "line": 12,
+                    "body": "Why?"
+                }),
+                &threads,
+            )
+            .unwrap()
+        };
+        let event = |id: u64, created_at: &str| {
+            TimelineEntry::from_timeline_event(&json!({
+                "event": "commented",
+                "id": id,
+                "user": { "login": "bob" },
+                "created_at": created_at,
+                "body": "Comment"
+            }))
+            .unwrap()
+        };
+
+        let timeline = build_timeline(
+            vec![
+                event(1, "2024-05-01T09:00:00Z"),
+                event(2, "2024-05-01T11:00:00Z"),
+            ],
+            vec![
+                review_comment(20, None, "2024-05-01T10:00:00Z"),
+                review_comment(21, Some(20), "2024-05-01T12:00:00Z"),
+            ],
+        );
+
+        let ids: Vec<Option<u64>> = timeline.iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, [Some(1), Some(20), Some(21), Some(2)]);
+        assert_eq!(timeline[1].action(), "commented on src/main.rs:12");
+        assert_eq!(timeline[2].action(), "replied");
+        assert!(timeline[2].thread().unwrap().resolved);
+    }
+
+    #[test]
+    fn test_review_threads() {
+        let threads = review_threads(&json!({
+            "data": { "repository": { "pullRequest": { "reviewThreads": { "nodes": [
+                { "id": "PRRT_1", "isResolved": false, "comments": { "nodes": [{ "databaseId": 20 }] } }
+            ] } } } }
+        }));
+        assert_eq!(
+            threads.get(&20),
+            Some(&ReviewThread {
+                id: "PRRT_1".to_string(),
+                resolved: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_next_threads_cursor() {
+        let page = |has_next: bool| {
+            json!({
+                "data": { "repository": { "pullRequest": { "reviewThreads": {
+                    "pageInfo": { "hasNextPage": has_next, "endCursor": "Y3Vyc29y" },
+                    "nodes": []
+                } } } }
+            })
+        };
+        assert_eq!(
+            next_threads_cursor(&page(true)),
+            Some("Y3Vyc29y".to_string())
+        );
+        assert_eq!(next_threads_cursor(&page(false)), None);
+        assert_eq!(next_threads_cursor(&json!({ "data": null })), None);
+    }
+}
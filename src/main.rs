@@ -1,53 +1,968 @@
+mod cli;
 mod core;
 mod ui;
+use crate::cli::args::Cli;
+use crate::cli::commands;
+use crate::core::browser::open_url;
+use crate::core::clipboard::copy_to_clipboard;
 use crate::core::config::{load_config, save_config};
+use crate::core::editor::{edit_text, editor_command};
 use crate::core::input_mode::InputMode;
+use crate::core::signal::listen_for_shutdown;
 use crate::ui::layout::ui;
-use core::app::App;
-use crossterm::{
-    event::{self, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use crate::ui::mouse::{Regions, Target};
+use crate::ui::terminal::{install_panic_hook, TerminalGuard};
+use clap::Parser;
+use core::app::{App, SyncResult};
+use core::dashboard::Dashboard;
+use core::errors::PullRequestError;
+use core::field::Field;
+use core::keymap::{self, Action, Context};
+use core::palette::CommandPalette;
+use core::screen::Screen;
+use core::text_input::TextInput;
+use core::timeline::reaction_emoji;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::{self, Sender};
+use std::time::{Duration, Instant};
+use std::{env, io, process};
 use tokio::runtime::Runtime;
+use tui_textarea::TextArea;
+
+const TICK_RATE: Duration = Duration::from_millis(250);
+/// Lines one notch of the mouse wheel scrolls text by.
+const SCROLL_LINES: i16 = 3;
+/// Lines `PageUp` and `PageDown` scroll the message log by.
+const MESSAGE_PAGE_LINES: usize = 5;
+
+type SyncSender = Sender<Result<SyncResult, PullRequestError>>;
+type DashboardSender = Sender<Result<Dashboard, PullRequestError>>;
 
 fn sync_github_repo_info(app: &mut App, runtime: &Runtime) -> Result<(), String> {
-    let result = runtime.block_on(app.fetch_github_repo_info());
+    let result = runtime.block_on(app.fetch_sync());
     match result {
-        Ok(repo) => {
-            if let Some(link) = repo.html_url {
-                app.github_repository.set_url(link.to_string());
+        Ok(sync) => {
+            app.apply_sync(sync);
+            Ok(())
+        }
+        Err(e) => Err(app.error_message(&e)),
+    }
+}
+
+/// Shows the cached repository and pull request right away, then refreshes
+/// them on the runtime. The event loop applies the result when it arrives.
+fn start_background_sync(app: &mut App, runtime: &Runtime, sender: &SyncSender) {
+    if let Ok(sync) = runtime.block_on(app.cached().fetch_sync()) {
+        app.apply_sync(sync);
+    }
+    let background = app.sync_snapshot();
+    let sender = sender.clone();
+    runtime.spawn(async move {
+        let _ = sender.send(background.fetch_sync().await);
+    });
+}
+
+fn sync_existing_pull_request(app: &mut App, runtime: &Runtime) -> Result<(), String> {
+    let result = runtime.block_on(app.fetch_existing_pull_request());
+    match result {
+        Ok(existing) => {
+            let (pr, status) = existing.unzip();
+            app.current_pull_request = pr;
+            app.pull_request_status = status;
+            Ok(())
+        }
+        Err(e) => Err(app.error_message(&e)),
+    }
+}
+
+fn open_repository_chooser(app: &mut App, runtime: &Runtime) -> Result<(), String> {
+    let result = runtime.block_on(app.fetch_github_repositories());
+    match result {
+        Ok(repos) => {
+            app.repositories = repos
+                .into_iter()
+                .filter_map(|repo| repo.full_name)
+                .collect();
+            let current = format!("{}/{}", app.repo_owner, app.repo_name);
+            app.selected_repository = app
+                .repositories
+                .iter()
+                .position(|name| *name == current)
+                .unwrap_or(0);
+            app.show_repo_popup = true;
+            Ok(())
+        }
+        Err(e) => Err(app.error_message(&e)),
+    }
+}
+
+/// Expands the log tail of the selected failed check, or collapses it.
+fn toggle_check_log(app: &mut App, runtime: &Runtime) {
+    let Some(check) = app.selected_check() else {
+        return;
+    };
+    if check.log_tail.is_some() {
+        if let Some(check) = app.selected_check_mut() {
+            check.log_tail = None;
+        }
+        return;
+    }
+    if !check.is_failure() {
+        app.set_error(format!(
+            "{} did not fail, there is no log to show",
+            check.name
+        ));
+        return;
+    }
+    let Some(job) = check.job else {
+        app.set_error(format!(
+            "Logs are only available for GitHub Actions checks, see {}",
+            check.url.as_deref().unwrap_or("the check on GitHub")
+        ));
+        return;
+    };
+    match runtime.block_on(app.fetch_check_log(job)) {
+        Ok(lines) => {
+            if let Some(check) = app.selected_check_mut() {
+                check.log_tail = Some(lines);
+            }
+        }
+        Err(e) => app.set_error(format!(
+            "Failed to fetch the log: {}",
+            app.error_message(&e)
+        )),
+    }
+}
+
+fn open_notifications(app: &mut App, runtime: &Runtime) -> Result<(), String> {
+    let result = runtime.block_on(app.fetch_notifications());
+    match result {
+        Ok(items) => {
+            app.notifications = items;
+            app.selected_notification = 0;
+            app.screen = Screen::Notifications;
+            Ok(())
+        }
+        Err(e) => Err(app.error_message(&e)),
+    }
+}
+
+/// Switches to `repository` and shows its pull request `number`.
+fn open_pull_request(app: &mut App, runtime: &Runtime, repository: &str, number: u64) {
+    let Some((owner, name)) = repository.split_once('/') else {
+        return;
+    };
+    app.set_repository(owner.to_string(), name.to_string());
+    app.reset();
+    app.opened_pull_request = Some(number);
+    match runtime.block_on(app.fetch_sync()) {
+        Ok(sync) => {
+            app.apply_sync(sync);
+            app.screen = Screen::Main;
+            app.set_success(format!("Showing {}#{} ✅", repository, number));
+        }
+        Err(e) => app.set_error(app.error_message(&e)),
+    }
+}
+
+/// Inserts bracketed paste into whichever input has the focus.
+fn paste(app: &mut App, text: &str) {
+    if app.show_pat_popup {
+        app.pat_input.insert_str(text.trim());
+    } else if let Some(ref mut palette) = app.palette {
+        palette.paste(text);
+    } else if let Some(ref mut command_line) = app.command_line {
+        command_line.paste(text);
+    } else if app.screen == Screen::Timeline && app.composing_reply {
+        app.reply_text_area.insert_str(text);
+    } else if app.screen == Screen::Main && app.input_mode == InputMode::Editing {
+        app.paste(text);
+    }
+}
+
+/// Opens the current field in `$VISUAL` or `$EDITOR`, handing the terminal
+/// over until the editor exits.
+fn edit_in_editor(app: &mut App, guard: &mut TerminalGuard) -> Result<(), String> {
+    let Some(file_name) = app.editor_file_name() else {
+        return Err("Only the title and description can be edited in an editor".to_string());
+    };
+    let text = app.form.value(app.field());
+    let command = editor_command()?;
+    match guard.suspend(|| edit_text(&command, &text, file_name)) {
+        Ok(Ok(edited)) => {
+            app.apply_editor_text(edited);
+            Ok(())
+        }
+        Ok(Err(e)) => Err(format!("Failed to edit with {}: {}", command.join(" "), e)),
+        Err(e) => Err(format!("Failed to restore the terminal: {}", e)),
+    }
+}
+
+/// Edits the command line, running the named action of the current context
+/// on Enter.
+fn handle_command_line_key(app: &mut App, runtime: &Runtime, key: KeyEvent) {
+    let Some(ref mut command_line) = app.command_line else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.command_line = None,
+        KeyCode::Enter => {
+            let command = command_line.text();
+            app.command_line = None;
+            let context = app.key_context();
+            match Action::from_command(&command, context) {
+                Some(action) => run_context_action(app, runtime, context, action),
+                None if command.trim().is_empty() => {}
+                None => app.set_error(format!("Unknown command \"{}\"", command.trim())),
+            }
+        }
+        _ => {
+            command_line.input(key);
+        }
+    }
+}
+
+/// `message` followed by the hints of the keys bound in the keymap, if any.
+fn with_instructions(message: String, hints: impl IntoIterator<Item = Option<String>>) -> String {
+    let instructions = keymap::instructions(hints);
+    if instructions.is_empty() {
+        message
+    } else {
+        format!("{}\n {}", message, instructions)
+    }
+}
+
+/// Filters the palette as the query is typed and runs the selected action
+/// on the submit key. The query takes text, so it moves and closes with
+/// the keys of the form and the selection keys that don't type a character.
+fn handle_palette_key(app: &mut App, runtime: &Runtime, key: KeyEvent) {
+    let action = app
+        .keymap
+        .action(Context::Editing, &key)
+        .or_else(|| app.keymap.typing_action(Context::Normal, &key));
+    let Some(ref mut palette) = app.palette else {
+        return;
+    };
+    match action {
+        Some(Action::StopEditing) => app.palette = None,
+        Some(Action::Submit) => {
+            let context = palette.context;
+            let action = palette.selected_action();
+            app.palette = None;
+            if let Some(action) = action {
+                run_context_action(app, runtime, context, action);
+            }
+        }
+        Some(Action::SelectNext) => palette.select_next(),
+        Some(Action::SelectPrevious) => palette.select_previous(),
+        _ => palette.input(key),
+    }
+}
+
+/// Runs `action` the way its key does in `context`, whether it came from
+/// the key itself, the command line or the palette.
+fn run_context_action(app: &mut App, runtime: &Runtime, context: Context, action: Action) {
+    match action {
+        Action::CommandLine => {
+            app.clear_message();
+            app.command_line = Some(TextInput::single_line());
+        }
+        Action::CommandPalette => {
+            app.clear_message();
+            app.palette = Some(CommandPalette::new(context));
+        }
+        Action::ShowHelp if context != Context::Help => open_help(app),
+        _ => match context {
+            Context::Normal => run_action(app, runtime, action),
+            Context::Confirm => run_confirm_action(app, runtime, action),
+            Context::Inbox => run_inbox_action(app, runtime, action),
+            Context::Dashboard => run_dashboard_action(app, runtime, action),
+            Context::Timeline => run_timeline_action(app, runtime, action),
+            Context::Messages => run_messages_action(app, action),
+            Context::Help => run_help_action(app, action),
+            // Keys typed into a field or a reply are handled with the text.
+            Context::Editing | Context::Reply => {}
+        },
+    }
+}
+
+/// Runs a Normal mode action.
+fn run_action(app: &mut App, runtime: &Runtime, action: Action) {
+    match action {
+        Action::Quit => {
+            app.show_exit_popup = true;
+        }
+        Action::Edit => {
+            app.clear_message();
+            if app.is_showing_pull_request() {
+                app.edit_existing_pull_request();
+            } else {
+                app.enter_edit_mode(app.current_field);
+            }
+        }
+        Action::UpdateBranch => {
+            let Some(number) = app.current_pull_request.as_ref().map(|pr| pr.number) else {
+                app.set_error("There is no pull request to update".to_string());
+                return;
+            };
+            app.clear_message();
+            match runtime.block_on(app.update_github_pull_request_branch(number)) {
+                Ok(_) => app.set_success(format!(
+                    "Branch of pull request #{} is being updated with {} ✅",
+                    number,
+                    app.form.value(Field::TargetBranch)
+                )),
+                Err(e) => app.set_error(format!(
+                    "Failed to update branch: {}",
+                    app.error_message(&e)
+                )),
+            }
+        }
+        Action::NewPullRequest => {
+            app.opened_pull_request = None;
+            app.reset();
+            app.clear_message();
+            if app.github_repository.get_default_branch().is_empty() {
+                if let Err(e) = sync_github_repo_info(app, runtime) {
+                    app.set_error(e);
+                }
+            }
+            app.enter_edit_mode(0);
+        }
+        Action::SelectNext if app.is_showing_pull_request() => app.select_next_check(),
+        Action::SelectPrevious if app.is_showing_pull_request() => app.select_previous_check(),
+        Action::SelectNext => {
+            app.current_field = app.field().next().index();
+        }
+        Action::SelectPrevious => {
+            app.current_field = app.field().previous().index();
+        }
+        Action::Open if app.is_showing_pull_request() => {
+            app.clear_message();
+            toggle_check_log(app, runtime);
+        }
+        Action::ShowTimeline if app.is_showing_pull_request() => {
+            app.clear_message();
+            if let Some(number) = app.current_pull_request.as_ref().map(|pr| pr.number) {
+                if let Err(e) = open_timeline(app, runtime, number) {
+                    app.set_error(e);
+                }
+            }
+        }
+        Action::RerunChecks if app.is_showing_pull_request() => {
+            app.clear_message();
+            let checks = app.checks().to_vec();
+            match runtime.block_on(app.rerun_failed_checks(&checks)) {
+                Ok(runs) => app.set_success(with_instructions(
+                    format!("Re-running the failed jobs of {} workflow run(s) ✅", runs),
+                    [app.keymap.hint(&[Action::Sync], "sync the checks")],
+                )),
+                Err(e) => app.set_error(format!(
+                    "Failed to re-run checks: {}",
+                    app.error_message(&e)
+                )),
+            }
+        }
+        Action::TogglePreview if !app.is_showing_pull_request() => {
+            app.show_preview = !app.show_preview;
+        }
+        Action::Open => app.set_error("There is no pull request to show the checks of".to_string()),
+        Action::ShowTimeline => {
+            app.set_error("There is no pull request to show the timeline of".to_string())
+        }
+        Action::RerunChecks => {
+            app.set_error("There is no pull request to re-run the checks of".to_string())
+        }
+        Action::TogglePreview => {
+            app.set_error("The preview is only shown next to the form".to_string())
+        }
+        Action::OpenDashboard => {
+            app.clear_message();
+            app.screen = Screen::Dashboard;
+        }
+        Action::OpenInbox => {
+            app.clear_message();
+            if let Err(e) = open_notifications(app, runtime) {
+                app.set_error(e);
+            }
+        }
+        Action::ChooseRepository => {
+            app.clear_message();
+            if let Err(e) = open_repository_chooser(app, runtime) {
+                app.set_error(e);
+            }
+        }
+        Action::OpenInBrowser => {
+            let url = app.pull_request_url();
+            open_in_browser(app, url);
+        }
+        Action::OpenRepository => {
+            let url = app.repository_url();
+            open_in_browser(app, Some(url));
+        }
+        Action::OpenCompare => {
+            let url = app.compare_url();
+            open_in_browser(app, Some(url));
+        }
+        Action::CopyUrl => {
+            let url = app.pull_request_url();
+            copy_url(app, url);
+        }
+        Action::CopyRepositoryUrl => {
+            let url = app.repository_url();
+            copy_url(app, Some(url));
+        }
+        Action::Sync => match sync_github_repo_info(app, runtime) {
+            Ok(_) => {
+                app.set_success("Repository has been synced successfully ✅".to_string());
+            }
+            Err(e) => {
+                app.set_error(e);
+            }
+        },
+        Action::ShowMessages => {
+            app.clear_message();
+            app.screen = Screen::Messages;
+        }
+        Action::ScrollMessagesUp => app.messages.scroll_up(MESSAGE_PAGE_LINES),
+        Action::ScrollMessagesDown => app.messages.scroll_down(MESSAGE_PAGE_LINES),
+        Action::CopyLastError => copy_last_error(app),
+        _ => {}
+    }
+}
+
+fn open_help(app: &mut App) {
+    app.show_help = true;
+    app.help_scroll = 0;
+}
+
+fn run_help_action(app: &mut App, action: Action) {
+    match action {
+        Action::Quit | Action::ShowHelp => app.show_help = false,
+        Action::SelectNext => app.scroll(1),
+        Action::SelectPrevious => app.scroll(-1),
+        _ => {}
+    }
+}
+
+/// Scrolls and copies from the full screen message log.
+fn run_messages_action(app: &mut App, action: Action) {
+    match action {
+        Action::Quit | Action::ShowMessages => app.screen = Screen::Main,
+        Action::SelectNext => app.messages.scroll_down(1),
+        Action::SelectPrevious => app.messages.scroll_up(1),
+        Action::ScrollMessagesUp => app.messages.scroll_up(MESSAGE_PAGE_LINES),
+        Action::ScrollMessagesDown => app.messages.scroll_down(MESSAGE_PAGE_LINES),
+        Action::CopyLastError => copy_last_error(app),
+        _ => {}
+    }
+}
+
+fn run_inbox_action(app: &mut App, runtime: &Runtime, action: Action) {
+    match action {
+        Action::Quit => app.screen = Screen::Main,
+        Action::SelectNext => app.select_next_notification(),
+        Action::SelectPrevious => app.select_previous_notification(),
+        Action::Open => {
+            app.clear_message();
+            let Some(item) = app.selected_notification().cloned() else {
+                return;
+            };
+            match item.pull_request {
+                Some(number) => open_pull_request(app, runtime, &item.repository, number),
+                None => app.set_error(format!("\"{}\" is not about a pull request", item.title)),
             }
-            if let Some(branch) = repo.default_branch {
-                app.github_repository.set_default_branch(branch.clone());
-                app.pull_request.target_branch = branch;
+        }
+        Action::MarkRead => {
+            let Some(thread_id) = app
+                .selected_notification()
+                .map(|item| item.thread_id.clone())
+            else {
+                return;
+            };
+            app.clear_message();
+            match runtime.block_on(app.mark_notification_read(&thread_id)) {
+                Ok(_) => {
+                    app.remove_notification(&thread_id);
+                    app.set_success("Marked as read ✅".to_string());
+                }
+                Err(e) => {
+                    app.set_error(format!("Failed to mark as read: {}", app.error_message(&e)))
+                }
+            }
+        }
+        Action::OpenInBrowser => {
+            let web_url = app.web_url();
+            let url = app
+                .selected_notification()
+                .map(|item| item.html_url(&web_url));
+            open_in_browser(app, url);
+        }
+        Action::Sync => {
+            app.clear_message();
+            if let Err(e) = open_notifications(app, runtime) {
+                app.set_error(e);
             }
+        }
+        _ => {}
+    }
+}
+
+fn run_dashboard_action(app: &mut App, runtime: &Runtime, action: Action) {
+    match action {
+        Action::Quit => app.screen = Screen::Main,
+        Action::SelectNext => app.select_next_dashboard_entry(),
+        Action::SelectPrevious => app.select_previous_dashboard_entry(),
+        Action::Open => {
+            app.clear_message();
+            if let Some(entry) = app.selected_dashboard_entry().cloned() {
+                open_pull_request(app, runtime, &entry.repository, entry.number);
+            }
+        }
+        Action::OpenInBrowser => {
+            let url = app
+                .selected_dashboard_entry()
+                .map(|entry| entry.url.clone());
+            open_in_browser(app, url);
+        }
+        Action::Sync => {
+            app.clear_message();
+            if !app.dashboard_loading {
+                app.dashboard_refreshed_at = None;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Types into the field being edited, except for the keys of the Editing
+/// actions.
+fn handle_editing_key(app: &mut App, runtime: &Runtime, guard: &mut TerminalGuard, key: KeyEvent) {
+    match app.keymap.action(Context::Editing, &key) {
+        Some(Action::ShowHelp) => open_help(app),
+        Some(Action::StopEditing) => {
+            app.input_mode = InputMode::Normal;
+        }
+        Some(Action::OpenEditor) => {
+            app.clear_message();
+            if let Err(e) = edit_in_editor(app, guard) {
+                app.set_error(e);
+            }
+        }
+        Some(Action::Submit) if !app.is_editing_description() => {
+            app.clear_message();
+            if let Err(e) = app.form.validate() {
+                app.set_error(app.error_message(&e));
+                return;
+            }
+            app.confirm_pull_request();
+            match runtime.block_on(app.fetch_comparison()) {
+                Ok(comparison) => app.comparison = Some(comparison),
+                Err(e) => {
+                    app.comparison = None;
+                    app.set_error(format!(
+                        "Failed to compare branches: {}",
+                        app.error_message(&e)
+                    ));
+                }
+            }
+        }
+        Some(Action::NextField) => {
+            app.current_field = app.field().next().index();
+        }
+        Some(Action::PreviousField) => {
+            app.current_field = app.field().previous().index();
+        }
+        _ => {
+            app.input_key(key);
+        }
+    }
+}
+
+/// Clicks focus form fields and press popup buttons, and the wheel scrolls
+/// whatever is in front. Returns whether the exit popup was confirmed.
+fn handle_mouse(app: &mut App, runtime: &Runtime, regions: &Regions, mouse: MouseEvent) -> bool {
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            match regions.target_at(mouse.column, mouse.row) {
+                Some(Target::Field(field)) => {
+                    app.command_line = None;
+                    app.enter_edit_mode(field.index());
+                }
+                Some(Target::Button(action)) => run_confirm_action(app, runtime, action),
+                Some(Target::Exit(exit)) => {
+                    if exit {
+                        return true;
+                    }
+                    app.show_exit_popup = false;
+                }
+                Some(Target::Popup) | None => {}
+            }
+        }
+        MouseEventKind::ScrollDown => app.scroll(SCROLL_LINES),
+        MouseEventKind::ScrollUp => app.scroll(-SCROLL_LINES),
+        _ => {}
+    }
+    false
+}
+
+/// Runs an action of the confirmation popup, from its key or its button.
+fn run_confirm_action(app: &mut App, runtime: &Runtime, action: Action) {
+    match action {
+        Action::Confirm => {
+            app.input_mode = InputMode::Normal;
+            app.show_confirm_popup = false;
+
+            if let Some(number) = app.editing_pull_request {
+                match runtime.block_on(app.update_github_pull_request(number)) {
+                    Ok(_) => {
+                        app.reset();
+                        if let Err(e) = sync_existing_pull_request(app, runtime) {
+                            app.set_error(e);
+                        } else {
+                            app.set_success(format!(
+                                "Pull request #{} updated successfully ✅",
+                                number
+                            ));
+                        }
+                    }
+                    Err(e) => {
+                        app.set_error(format!(
+                            "Failed to update pull request: {}",
+                            app.error_message(&e)
+                        ));
+                    }
+                }
+                return;
+            }
+
+            let result = runtime.block_on(app.create_github_pull_request());
+            match result {
+                Ok(pr) => {
+                    let url_str = match pr.html_url {
+                        Some(ref url) => url.to_string(),
+                        None => "No URL available".to_string(),
+                    };
+                    app.reset();
+                    app.current_pull_request = Some(pr);
+                    if let Ok(Some((pr, status))) =
+                        runtime.block_on(app.fetch_existing_pull_request())
+                    {
+                        app.current_pull_request = Some(pr);
+                        app.pull_request_status = Some(status);
+                    }
+                    app.set_success(with_instructions(
+                        format!("Pull request created successfully ✅\n Url: {}", url_str),
+                        [
+                            app.keymap.hint(&[Action::OpenInBrowser], "open it"),
+                            app.keymap.hint(&[Action::CopyUrl], "copy the URL"),
+                        ],
+                    ));
+                }
+                Err(e) => {
+                    app.set_error(format!(
+                        "Failed to create pull request: {}",
+                        app.error_message(&e)
+                    ));
+                }
+            }
+        }
+        Action::ContinueEditing => {
+            app.input_mode = InputMode::Editing;
+            app.show_confirm_popup = false;
+        }
+        Action::Cancel => {
+            app.input_mode = InputMode::Normal;
+            app.show_confirm_popup = false;
+        }
+        _ => {}
+    }
+}
+
+fn open_timeline(app: &mut App, runtime: &Runtime, number: u64) -> Result<(), String> {
+    let result = runtime.block_on(app.fetch_timeline(number));
+    match result {
+        Ok(entries) => {
+            if app.timeline_pull_request != Some(number) {
+                app.selected_timeline_entry = entries.len().saturating_sub(1);
+            }
+            app.timeline = entries;
+            app.timeline_pull_request = Some(number);
+            app.selected_timeline_entry = app
+                .selected_timeline_entry
+                .min(app.timeline.len().saturating_sub(1));
+            app.screen = Screen::Timeline;
             Ok(())
         }
-        Err(e) => Err(format!("Error {:?}", e)),
+        Err(e) => Err(app.error_message(&e)),
+    }
+}
+
+/// Types into the reply being written on the timeline, except for the keys
+/// of the Reply actions.
+fn handle_reply_key(app: &mut App, runtime: &Runtime, key: KeyEvent) {
+    let Some(number) = app.timeline_pull_request else {
+        return;
+    };
+    match app.keymap.action(Context::Reply, &key) {
+        Some(Action::ShowHelp) => open_help(app),
+        Some(Action::CancelReply) => app.composing_reply = false,
+        Some(Action::SendReply) => {
+            let body = app.reply_text_area.lines().join("\n");
+            let entry = app.selected_timeline_entry().cloned();
+            app.clear_message();
+            match runtime.block_on(app.reply_to_timeline(number, entry.as_ref(), &body)) {
+                Ok(_) => {
+                    app.composing_reply = false;
+                    app.reply_text_area = TextArea::default();
+                    if let Err(e) = open_timeline(app, runtime, number) {
+                        app.set_error(e);
+                    } else {
+                        app.set_success("Reply posted ✅".to_string());
+                    }
+                }
+                Err(e) => app.set_error(format!(
+                    "Failed to post the reply: {}",
+                    app.error_message(&e)
+                )),
+            }
+        }
+        _ => {
+            app.reply_text_area.input(key);
+        }
+    }
+}
+
+fn run_timeline_action(app: &mut App, runtime: &Runtime, action: Action) {
+    let Some(number) = app.timeline_pull_request else {
+        return;
+    };
+    match action {
+        Action::Quit => app.screen = Screen::Main,
+        Action::SelectNext => app.select_next_timeline_entry(),
+        Action::SelectPrevious => app.select_previous_timeline_entry(),
+        Action::Reply => {
+            app.clear_message();
+            app.composing_reply = true;
+        }
+        Action::ResolveThread => {
+            let Some(thread) = app
+                .selected_timeline_entry()
+                .and_then(|entry| entry.thread())
+                .cloned()
+            else {
+                app.set_error("Select a review comment to resolve its thread".to_string());
+                return;
+            };
+            app.clear_message();
+            match runtime.block_on(app.set_review_thread_resolved(&thread, !thread.resolved)) {
+                Ok(_) => {
+                    if let Err(e) = open_timeline(app, runtime, number) {
+                        app.set_error(e);
+                    } else if thread.resolved {
+                        app.set_success("Thread unresolved ✅".to_string());
+                    } else {
+                        app.set_success("Thread resolved ✅".to_string());
+                    }
+                }
+                Err(e) => app.set_error(format!(
+                    "Failed to update the thread: {}",
+                    app.error_message(&e)
+                )),
+            }
+        }
+        Action::OpenInBrowser => {
+            let url = app.pull_request_url();
+            open_in_browser(app, url);
+        }
+        Action::Sync => {
+            app.clear_message();
+            if let Err(e) = open_timeline(app, runtime, number) {
+                app.set_error(e);
+            }
+        }
+        action => {
+            if let Some(content) = action.reaction() {
+                react(app, runtime, number, content);
+            }
+        }
+    }
+}
+
+/// Adds the `content` reaction to the selected comment of the timeline of
+/// pull request `number`.
+fn react(app: &mut App, runtime: &Runtime, number: u64, content: &str) {
+    let Some(entry) = app
+        .selected_timeline_entry()
+        .filter(|entry| entry.is_comment())
+        .cloned()
+    else {
+        app.set_error("Select a comment to react to".to_string());
+        return;
+    };
+    app.clear_message();
+    match runtime.block_on(app.add_reaction(&entry, content)) {
+        Ok(_) => {
+            if let Err(e) = open_timeline(app, runtime, number) {
+                app.set_error(e);
+            } else {
+                app.set_success(format!("Reacted with {} ✅", reaction_emoji(content)));
+            }
+        }
+        Err(e) => app.set_error(format!("Failed to react: {}", app.error_message(&e))),
+    }
+}
+
+/// Shows the cached dashboard if there is nothing to show yet, then
+/// refreshes it on the runtime.
+fn start_dashboard_refresh(app: &mut App, runtime: &Runtime, sender: &DashboardSender) {
+    if app.dashboard.is_empty() {
+        if let Ok(dashboard) = runtime.block_on(app.cached().fetch_dashboard()) {
+            app.dashboard = dashboard.entries;
+        }
+    }
+    app.dashboard_loading = true;
+    let background = app.sync_snapshot();
+    let sender = sender.clone();
+    runtime.spawn(async move {
+        let _ = sender.send(background.fetch_dashboard().await);
+    });
+}
+
+fn open_in_browser(app: &mut App, url: Option<String>) {
+    match url {
+        Some(url) => match open_url(&url) {
+            Ok(_) => app.set_success(format!("Opened {} in the browser ✅", url)),
+            Err(e) => app.set_error(format!("Failed to open the browser: {}", e)),
+        },
+        None => app.set_error("There is no pull request to open".to_string()),
+    }
+}
+
+fn copy_last_error(app: &mut App) {
+    let Some(error) = app
+        .messages
+        .last_error()
+        .map(|message| message.text.clone())
+    else {
+        app.set_error("There is no error to copy".to_string());
+        return;
+    };
+    match copy_to_clipboard(&error) {
+        Ok(_) => app.set_success("Copied the last error to the clipboard ✅".to_string()),
+        Err(e) => app.set_error(format!("Failed to copy to the clipboard: {}", e)),
+    }
+}
+
+fn copy_url(app: &mut App, url: Option<String>) {
+    match url {
+        Some(url) => match copy_to_clipboard(&url) {
+            Ok(_) => app.set_success(format!("Copied {} to the clipboard ✅", url)),
+            Err(e) => app.set_error(format!("Failed to copy to the clipboard: {}", e)),
+        },
+        None => app.set_error("There is no pull request to copy".to_string()),
     }
 }
 
 fn main() -> Result<(), io::Error> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let cli = Cli::parse();
+    if let Some(ref path) = cli.directory {
+        env::set_current_dir(path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("cannot change to '{}': {}", path.display(), e),
+            )
+        })?;
+    }
 
-    let config = load_config();
     let mut app = App::new();
     let runtime = tokio::runtime::Runtime::new().unwrap();
+    if let Some((owner, name)) = cli.repo {
+        app.set_repository(owner, name);
+    }
+
+    if let Some(command) = cli.command {
+        let web_url = app.web_url();
+        if let Err(e) = commands::run(command, cli.json, app, &runtime) {
+            eprintln!("Error: {}", e.message_with_hint(None, &web_url));
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // A config that doesn't parse was already reported by `App::new()`.
+    let config = load_config().ok().flatten();
+    install_panic_hook();
+    let mut guard = TerminalGuard::new(config.as_ref().is_none_or(|config| config.mouse))?;
+
+    let shutdown = listen_for_shutdown(&runtime);
+    let (sync_sender, sync_receiver) = mpsc::channel();
+    let (dashboard_sender, dashboard_receiver): (DashboardSender, _) = mpsc::channel();
 
     if config.is_none() {
         app.show_pat_popup = true;
+    } else if !app.has_repository() {
+        if let Err(e) = open_repository_chooser(&mut app, &runtime) {
+            app.set_error(e);
+        }
+    } else {
+        start_background_sync(&mut app, &runtime, &sync_sender);
     }
 
+    let mut regions = Regions::default();
     loop {
-        terminal.draw(|f| ui(f, &app))?;
-        if let Event::Key(key) = event::read()? {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        if let Ok(result) = sync_receiver.try_recv() {
+            match result {
+                Ok(sync) => {
+                    app.apply_sync(sync);
+                }
+                Err(e) => app.set_error(app.error_message(&e)),
+            }
+        }
+        if let Ok(result) = dashboard_receiver.try_recv() {
+            app.dashboard_loading = false;
+            app.dashboard_refreshed_at = Some(Instant::now());
+            match result {
+                Ok(dashboard) => {
+                    app.dashboard = dashboard.entries;
+                    app.selected_dashboard_entry = app
+                        .selected_dashboard_entry
+                        .min(app.dashboard.len().saturating_sub(1));
+                    for failure in dashboard.failures {
+                        app.set_error(failure);
+                    }
+                }
+                Err(e) => app.set_error(format!(
+                    "Failed to refresh the dashboard: {}",
+                    app.error_message(&e)
+                )),
+            }
+        }
+        if app.dashboard_refresh_due(Instant::now()) {
+            start_dashboard_refresh(&mut app, &runtime, &dashboard_sender);
+        }
+        guard.terminal().draw(|f| regions = ui(f, &app))?;
+        app.clamp_help_scroll(regions.help_scroll_max);
+        if let Some(max) = regions.messages_scroll_max {
+            app.messages.clamp_scroll(max);
+        }
+        if !event::poll(TICK_RATE)? {
+            continue;
+        }
+        let event = event::read()?;
+        if let Event::Paste(ref text) = event {
+            paste(&mut app, text);
+        }
+        if let Event::Mouse(mouse) = event {
+            if handle_mouse(&mut app, &runtime, &regions, mouse) {
+                break;
+            }
+        }
+        if let Event::Key(key) = event {
             if app.show_pat_popup {
                 match key.code {
                     KeyCode::Backspace => {
@@ -56,13 +971,24 @@ fn main() -> Result<(), io::Error> {
                     KeyCode::Enter => {
                         if !app.pat_input.is_empty() {
                             app.config_pat = app.pat_input.lines().join("\n");
+                            app.token_user = None;
                             app.clear_message();
-                            match sync_github_repo_info(&mut app, &runtime) {
+                            let result = if app.has_repository() {
+                                sync_github_repo_info(&mut app, &runtime)
+                            } else {
+                                open_repository_chooser(&mut app, &runtime)
+                            };
+                            match result {
                                 Ok(_) => {
                                     app.show_pat_popup = false;
-                                    save_config(&app.config_pat)
-                                        .expect("Failed to save the configuration");
-                                    app.set_success("PAT saved successfully ✅".to_string());
+                                    match save_config(&app.config_pat) {
+                                        Ok(()) => {
+                                            app.set_success("PAT saved successfully ✅".to_string())
+                                        }
+                                        Err(e) => {
+                                            app.set_error(format!("Failed to save the PAT: {}", e))
+                                        }
+                                    }
                                 }
                                 Err(e) => {
                                     app.set_error(e);
@@ -79,6 +1005,35 @@ fn main() -> Result<(), io::Error> {
                 }
                 continue;
             }
+            if app.show_repo_popup {
+                let action = app.keymap.action(Context::Normal, &key);
+                match key.code {
+                    _ if action == Some(Action::SelectNext) => app.select_next_repository(),
+                    _ if action == Some(Action::SelectPrevious) => app.select_previous_repository(),
+                    _ if action == Some(Action::Open) => {
+                        if !app.choose_selected_repository() {
+                            continue;
+                        }
+                        app.clear_message();
+                        match sync_github_repo_info(&mut app, &runtime) {
+                            Ok(_) => {
+                                app.set_success(format!(
+                                    "Switched to {}/{} ✅",
+                                    app.repo_owner, app.repo_name
+                                ));
+                            }
+                            Err(e) => {
+                                app.set_error(e);
+                            }
+                        }
+                    }
+                    _ if action == Some(Action::Quit) => {
+                        app.show_repo_popup = false;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
             if app.show_exit_popup {
                 match key.code {
                     KeyCode::Char('y') => {
@@ -91,121 +1046,25 @@ fn main() -> Result<(), io::Error> {
                 }
                 continue;
             }
-            match app.input_mode {
-                InputMode::Normal => match key.code {
-                    KeyCode::Esc => {
-                        app.show_exit_popup = true;
-                    }
-                    KeyCode::Char('e') => {
-                        app.clear_message();
-                        app.enter_edit_mode(app.current_field);
-                    }
-                    KeyCode::Char('n') => {
-                        app.reset();
-                        app.clear_message();
-                        app.enter_edit_mode(0);
-                        if app.github_repository.get_default_branch().is_empty() {
-                            if let Err(e) = sync_github_repo_info(&mut app, &runtime) {
-                                app.set_error(e);
-                            }
-                        }
-                    }
-                    KeyCode::Down => {
-                        app.current_field = (app.current_field + 1) % 4;
-                    }
-                    KeyCode::Up => {
-                        app.current_field = (app.current_field + 3) % 4;
-                    }
-                    KeyCode::Char('s') => match sync_github_repo_info(&mut app, &runtime) {
-                        Ok(_) => {
-                            app.set_success(
-                                "Repository has been synced successfully ✅".to_string(),
-                            );
-                        }
-                        Err(e) => {
-                            app.set_error(e);
-                        }
-                    },
-                    _ => {}
-                },
-                InputMode::Editing => match key.code {
-                    KeyCode::Esc => {
-                        app.input_mode = InputMode::Normal;
-                    }
-                    KeyCode::Char(c) => {
-                        if app.is_editing_description() {
-                            app.description_text_area.input(key);
-                        } else {
-                            let current_field = app.get_current_field_mut();
-                            current_field.push(c);
-                        }
-                    }
-                    KeyCode::Backspace => {
-                        if app.is_editing_description() {
-                            app.description_text_area.input(key);
-                        } else {
-                            let current_field = app.get_current_field_mut();
-                            current_field.pop();
-                        }
-                    }
-                    KeyCode::Enter => {
-                        if app.is_editing_description() {
-                            let current_field = app.get_current_field_mut();
-                            current_field.push('\n');
-                            app.description_text_area.input(key);
-                        } else {
-                            app.confirm_pull_request();
-                        }
-                    }
-                    KeyCode::Tab => {
-                        app.current_field = (app.current_field + 1) % 4;
-                    }
-                    KeyCode::BackTab => {
-                        app.current_field = (app.current_field + 3) % 4;
-                    }
-                    _ => {}
-                },
-                InputMode::Creating => match key.code {
-                    KeyCode::Enter | KeyCode::Char('y') => {
-                        app.input_mode = InputMode::Normal;
-                        app.show_confirm_popup = false;
-                        app.pull_request.description = app.description_text_area.lines().join("\n");
-
-                        let result = runtime.block_on(app.create_github_pull_request());
-                        match result {
-                            Ok(pr) => {
-                                let url_str = match pr.html_url {
-                                    Some(ref url) => url.to_string(),
-                                    None => "No URL available".to_string(),
-                                };
-                                app.reset();
-                                app.set_success(format!(
-                                    "Pull request created successfully ✅\n Url: {}",
-                                    url_str
-                                ));
-                            }
-                            Err(e) => {
-                                app.set_error(format!("Failed to create pull request: {}", e));
-                            }
-                        }
-                    }
-                    KeyCode::Char('e') | KeyCode::Char('n') => {
-                        app.input_mode = InputMode::Editing;
-                        app.show_confirm_popup = false;
-                    }
-                    KeyCode::Esc => {
-                        app.input_mode = InputMode::Normal;
-                        app.show_confirm_popup = false;
+            if app.palette.is_some() {
+                handle_palette_key(&mut app, &runtime, key);
+                continue;
+            }
+            if app.command_line.is_some() {
+                handle_command_line_key(&mut app, &runtime, key);
+                continue;
+            }
+            match app.key_context() {
+                Context::Editing => handle_editing_key(&mut app, &runtime, &mut guard, key),
+                Context::Reply => handle_reply_key(&mut app, &runtime, key),
+                context => {
+                    if let Some(action) = app.keymap.action(context, &key) {
+                        run_context_action(&mut app, &runtime, context, action);
                     }
-                    _ => {}
-                },
+                }
             }
         }
     }
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
-
     Ok(())
 }
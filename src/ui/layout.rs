@@ -1,39 +1,300 @@
-use crate::ui::util::{centered_rect, inner_area};
+use crate::core::checks::CheckOutcome;
+use crate::core::compare::{diffstat_bar, Comparison};
+use crate::core::field::Field;
+use crate::core::help;
+use crate::core::keymap::{self, Action};
+use crate::core::message_log::Severity;
+use crate::core::pull_request::{ChecksState, PullRequestSummary, ReviewDecision};
+use crate::core::screen::Screen;
+use crate::core::theme::Theme;
+use crate::core::time::relative_time;
+use crate::core::timeline::{reaction_emoji, TimelineKind};
+use crate::ui::markdown::{links, render_markdown};
+use crate::ui::mouse::{Regions, Target};
+use crate::ui::responsive::{popup_rect, split_detail, wrap, ScreenLayout, MIN_HEIGHT, MIN_WIDTH};
+use crate::ui::util::inner_area;
 use crate::App;
 use crate::InputMode;
+use chrono::Utc;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use ratatui::widgets::{
+    Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Wrap,
+};
 use ratatui::{
-    style::{Color, Style},
+    buffer::Buffer,
+    style::{Color, Modifier, Style},
     Frame,
 };
+use std::time::Instant;
 
-pub fn ui(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(2)
-        .constraints(
-            [
-                Constraint::Percentage(20),
-                Constraint::Percentage(50),
-                Constraint::Percentage(25),
-                Constraint::Percentage(5),
-            ]
-            .as_ref(),
-        )
-        .split(f.area());
+const MAX_DIFFSTAT_FILES: usize = 10;
+/// Longer toasts are cut off, the whole message is in the log.
+const TOAST_MAX_HEIGHT: u16 = 6;
 
-    let block = Block::default()
+/// Draws the frame and returns where its clickable parts ended up.
+pub fn ui(f: &mut Frame, app: &App) -> Regions {
+    let mut regions = Regions::default();
+    let mut block = Block::default()
         .title("PRT: Pull Request TUI")
         .borders(Borders::ALL)
+        .border_style(app.theme.border)
         .border_type(ratatui::widgets::BorderType::Rounded);
+    if let Some(rate_limit) = app.rate_limit() {
+        let style = if rate_limit.remaining * 10 < rate_limit.limit {
+            app.theme.error
+        } else {
+            Style::default()
+        };
+        let mut text = format!(
+            " GitHub API: {}/{} ",
+            rate_limit.remaining, rate_limit.limit
+        );
+        if rate_limit.remaining == 0 {
+            text.push_str(&format!(
+                "(resets in {}m) ",
+                rate_limit.seconds_until_reset().div_ceil(60)
+            ));
+        }
+        block = block.title_bottom(Line::styled(text, style).right_aligned());
+    }
     f.render_widget(block, f.area());
 
+    let instructions = instructions(app);
+    let Some(layout) = ScreenLayout::new(f.area(), &instructions) else {
+        render_too_small(f, app);
+        return regions;
+    };
+    render_header(f, app, layout.header, layout.compact);
+
+    if app.screen == Screen::Messages {
+        render_message_log(f, app, layout.body.union(layout.message), &mut regions);
+    } else if app.screen == Screen::Notifications {
+        render_notifications(f, app, layout.body);
+    } else if app.screen == Screen::Dashboard {
+        render_dashboard(f, app, layout.body);
+    } else if app.screen == Screen::Timeline {
+        render_timeline(f, app, layout.body);
+    } else if app.is_showing_pull_request() {
+        render_pull_request(f, app, layout.body);
+    } else {
+        render_form(f, app, layout.body, &mut regions);
+    }
+
+    if let Some(ref command_line) = app.command_line {
+        render_message(
+            f,
+            &format!(":{}", command_line.text()),
+            Style::default(),
+            app,
+            layout.message,
+        );
+    } else if app.screen != Screen::Messages {
+        render_output(f, app, layout.message, &mut regions);
+    }
+
+    // Instructions
+    let instructions_paragraph = Paragraph::new(instructions)
+        .style(Style::default())
+        .wrap(Wrap { trim: false });
+    f.render_widget(instructions_paragraph, layout.instructions);
+
+    if app.screen != Screen::Messages {
+        render_toast(f, app, f.area());
+    }
+
+    if app.show_confirm_popup {
+        let popup_block = Block::default()
+            .title("Pull Request Confirmation")
+            .borders(Borders::ALL)
+            .border_style(app.theme.border)
+            .style(Style::default());
+
+        // On compact layouts a popup would leave clipped bits of the form
+        // around it, so it takes the whole body instead.
+        let area_confirm_popup = if layout.compact {
+            layout.body
+        } else if app.comparison.is_some() {
+            popup_rect(f.area(), 70, 60, 56, 14)
+        } else {
+            popup_rect(f.area(), 60, 12, 50, 6)
+        };
+        f.render_widget(Clear, area_confirm_popup);
+        f.render_widget(popup_block, area_confirm_popup);
+        regions.push(f.area(), Target::Popup);
+
+        let question = match app.editing_pull_request {
+            Some(number) => format!(
+                "Please confirm the update of PR #{} from {} to {} ",
+                number,
+                app.form.value(Field::SourceBranch),
+                app.form.value(Field::TargetBranch)
+            ),
+            None => format!(
+                "Please confirm PR creation from {} to {} ",
+                app.form.value(Field::SourceBranch),
+                app.form.value(Field::TargetBranch)
+            ),
+        };
+        let mut popup_text = vec![Line::from(question), Line::from("")];
+        if let Some(ref comparison) = app.comparison {
+            let width = inner_area(area_confirm_popup).width as usize;
+            popup_text.extend(comparison_lines(comparison, width, &app.theme));
+            popup_text.push(Line::from(""));
+        }
+        let (text_area, buttons_area) = split_buttons(inner_area(area_confirm_popup));
+        let popup_paragraph = Paragraph::new(popup_text)
+            .block(Block::default().borders(Borders::NONE))
+            .alignment(ratatui::layout::Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .scroll((app.confirm_scroll, 0));
+
+        f.render_widget(popup_paragraph, text_area);
+        let button = |label: &str, action: Action| {
+            let label = match app.keymap.keys(action).first() {
+                Some(key) => format!("{} [{}]", label, key),
+                None => label.to_string(),
+            };
+            (label, Target::Button(action))
+        };
+        render_buttons(
+            f,
+            buttons_area,
+            &[
+                button("Confirm", Action::Confirm),
+                button("Edit", Action::ContinueEditing),
+                button("Cancel", Action::Cancel),
+            ],
+            &mut regions,
+        );
+    }
+
+    if app.show_pat_popup {
+        // Over the body, so it doesn't land on the borders around it.
+        let area = popup_rect(layout.body, 50, 15, 40, 3);
+        f.render_widget(Clear, area);
+        regions.push(f.area(), Target::Popup);
+        let mut pat_input_text = app.pat_input.clone();
+        pat_input_text.set_block(
+            Block::default()
+                .title("Insert Github PAT")
+                .padding(Padding::new(1, 1, 0, 0))
+                .style(Style::default())
+                .borders(Borders::ALL)
+                .border_style(app.theme.border),
+        );
+        pat_input_text.set_cursor_style(app.theme.editing.add_modifier(Modifier::REVERSED));
+        pat_input_text.set_placeholder_text("Enter your Github PAT here");
+        pat_input_text.set_mask_char('*');
+        f.render_widget(&pat_input_text, area);
+    }
+
+    if app.show_repo_popup {
+        let area = popup_rect(f.area(), 50, 60, 40, 8);
+        f.render_widget(Clear, area);
+        regions.push(f.area(), Target::Popup);
+        let items: Vec<ListItem> = app
+            .repositories
+            .iter()
+            .map(|name| ListItem::new(name.as_str()))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title("Select Repository")
+                    .title_bottom(hints([
+                        app.keymap
+                            .hint(&[Action::SelectPrevious, Action::SelectNext], "move"),
+                        app.keymap.hint(&[Action::Open], "select"),
+                        app.keymap.hint(&[Action::Quit], "cancel"),
+                    ]))
+                    .borders(Borders::ALL)
+                    .border_style(app.theme.border),
+            )
+            .highlight_style(app.theme.focused)
+            .highlight_symbol("> ");
+        let mut state = ListState::default().with_selected(Some(app.selected_repository));
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    if app.show_help {
+        render_help(f, app, &mut regions);
+    }
+
+    // The palette can be opened from the help, so it goes on top of it.
+    if app.palette.is_some() {
+        render_palette(f, app, &mut regions);
+    }
+
+    if app.show_exit_popup {
+        let popup_block = Block::default()
+            .title("Exit Confirmation")
+            .borders(Borders::ALL)
+            .border_style(app.theme.border)
+            .style(Style::default());
+
+        let exit_area = popup_rect(layout.body, 40, 12, 36, 5);
+        f.render_widget(Clear, exit_area);
+        f.render_widget(popup_block, exit_area);
+        regions.push(f.area(), Target::Popup);
+
+        let (text_area, buttons_area) = split_buttons(inner_area(exit_area));
+        let popup_paragraph = Paragraph::new("Are you sure you want to exit?")
+            .block(Block::default().borders(Borders::NONE))
+            .alignment(ratatui::layout::Alignment::Center);
+
+        f.render_widget(popup_paragraph, text_area);
+        render_buttons(
+            f,
+            buttons_area,
+            &[
+                ("Exit [y]".to_string(), Target::Exit(true)),
+                ("Stay [n]".to_string(), Target::Exit(false)),
+            ],
+            &mut regions,
+        );
+    }
+
+    if !app.theme.color {
+        strip_colors(f.buffer_mut());
+    }
+    regions
+}
+
+/// Draws everything in the terminal's own colours, keeping attributes such
+/// as bold and reversed. Covers text coloured outside the theme, such as
+/// highlighted code in the markdown preview.
+fn strip_colors(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        cell.set_fg(Color::Reset);
+        cell.set_bg(Color::Reset);
+    }
+}
+
+/// The repository panel, or a single line of it on short terminals.
+fn render_header(f: &mut Frame, app: &App, area: Rect, compact: bool) {
+    if compact {
+        let mut header = vec![Span::styled(
+            format!("{}/{}", app.repo_owner, app.repo_name),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for detail in [
+            app.github_repository.get_default_branch(),
+            app.github_repository.get_url(),
+        ] {
+            if !detail.is_empty() {
+                header.push(Span::raw(format!(" · {}", detail)));
+            }
+        }
+        let header = Line::from(header);
+        f.render_widget(Paragraph::new(header), area);
+        return;
+    }
     let repository_block = Block::default()
         .title("Github Config")
         .padding(Padding::new(1, 0, 1, 0))
-        .borders(Borders::ALL);
+        .borders(Borders::ALL)
+        .border_style(app.theme.border);
     let text = vec![
         Line::from(Span::raw(format!("Owner: {}", app.repo_owner))),
         Line::from(Span::raw(format!("Repo: {}", app.repo_name))),
@@ -49,20 +310,411 @@ pub fn ui(f: &mut Frame, app: &App) {
     let paragraph = Paragraph::new(text)
         .block(repository_block)
         .style(Style::default());
+    f.render_widget(paragraph, area);
+}
+
+/// Shown instead of everything else below the minimum size.
+fn render_too_small(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let text = vec![
+        Line::styled("Terminal too small", app.theme.error),
+        Line::from(format!("{}x{}", area.width, area.height)),
+        Line::from(format!("Resize to at least {}x{}", MIN_WIDTH, MIN_HEIGHT)),
+    ];
+    let height = (text.len() as u16).min(area.height);
+    let area = Rect::new(
+        area.x,
+        area.y + (area.height - height) / 2,
+        area.width,
+        height,
+    );
+    f.render_widget(
+        Paragraph::new(text)
+            .alignment(ratatui::layout::Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
+/// Splits a popup into its text and the row of buttons at the bottom.
+fn split_buttons(area: Rect) -> (Rect, Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    (chunks[0], chunks[1])
+}
+
+/// Draws `buttons` centered on one row and records where each one is.
+fn render_buttons(f: &mut Frame, area: Rect, buttons: &[(String, Target)], regions: &mut Regions) {
+    const GAP: u16 = 3;
+    let labels: Vec<String> = buttons
+        .iter()
+        .map(|(label, _)| format!(" {} ", label))
+        .collect();
+    let width: u16 = labels
+        .iter()
+        .map(|label| label.chars().count() as u16)
+        .sum::<u16>()
+        + GAP * labels.len().saturating_sub(1) as u16;
+    let mut x = area.x + area.width.saturating_sub(width) / 2;
+    for (label, (_, target)) in labels.into_iter().zip(buttons) {
+        let button =
+            Rect::new(x, area.y, label.chars().count() as u16, area.height).intersection(area);
+        x += button.width + GAP;
+        f.render_widget(
+            Paragraph::new(label).style(Style::default().add_modifier(Modifier::REVERSED)),
+            button,
+        );
+        regions.push(button, *target);
+    }
+}
+
+/// The hints of a popup's keys, for its bottom border.
+fn hints(hints: impl IntoIterator<Item = Option<String>>) -> String {
+    hints.into_iter().flatten().collect::<Vec<_>>().join(", ")
+}
 
-    let repo_area = Layout::default()
+/// The command palette: the query on top and the matching actions below,
+/// each with its first key.
+fn render_palette(f: &mut Frame, app: &App, regions: &mut Regions) {
+    let Some(ref palette) = app.palette else {
+        return;
+    };
+    let area = popup_rect(f.area(), 60, 60, 50, 10);
+    f.render_widget(Clear, area);
+    regions.push(f.area(), Target::Popup);
+    let block = Block::default()
+        .title("Command Palette")
+        .title_bottom(hints([
+            app.keymap
+                .typing_hint(&[Action::SelectPrevious, Action::SelectNext], "move"),
+            app.keymap.typing_hint(&[Action::Submit], "run"),
+            app.keymap.typing_hint(&[Action::StopEditing], "cancel"),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(app.theme.border);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(100)].as_ref())
-        .split(chunks[0]);
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
 
-    f.render_widget(paragraph, repo_area[0]);
+    f.render_widget(
+        Paragraph::new(format!("> {}", palette.query.text())),
+        chunks[0],
+    );
 
-    let description_lines = app.pull_request.description.lines().count();
-    let description_height = description_lines.min(20) + 3;
+    let matches = palette.matches();
+    if matches.is_empty() {
+        f.render_widget(
+            Paragraph::new("No matching action").style(app.theme.muted),
+            chunks[1],
+        );
+        return;
+    }
+    let width = matches
+        .iter()
+        .map(|action| action.description().chars().count())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|action| {
+            let key = app
+                .keymap
+                .keys(*action)
+                .first()
+                .map(|key| format!("[{}]", key))
+                .unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:width$}  ", action.description(), width = width)),
+                Span::styled(key, app.theme.accent),
+                Span::styled(format!("  {}", action.name()), app.theme.muted),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(app.theme.focused)
+        .highlight_symbol("> ");
+    let mut state = ListState::default().with_selected(Some(palette.selected));
+    f.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+/// Every key of the current screen and mode, the form fields and the
+/// account in use, from the same actions the keys are dispatched to.
+fn render_help(f: &mut Frame, app: &App, regions: &mut Regions) {
+    let area = popup_rect(f.area(), 70, 80, 56, 16);
+    f.render_widget(Clear, area);
+    regions.push(f.area(), Target::Popup);
+    let (mode, _) = mode_hints(app);
+    let close = [
+        app.keymap.hint(&[Action::Quit, Action::ShowHelp], "close"),
+        app.keymap
+            .hint(&[Action::SelectPrevious, Action::SelectNext], "scroll"),
+    ];
+    let block = Block::default()
+        .title(format!("Help: {}", mode))
+        .title_bottom(hints(close))
+        .borders(Borders::ALL)
+        .border_style(app.theme.border)
+        .padding(Padding::horizontal(1));
+
+    let token_user = match app.token_user {
+        Some(Ok(ref login)) => login.clone(),
+        Some(Err(ref e)) => format!("unknown, {}", e),
+        None if app.config_pat.is_empty() => "no token".to_string(),
+        None => "unknown until the next sync".to_string(),
+    };
+    let account = [
+        ("Config", help::config_location()),
+        ("Host", help::host(&app.api_url).to_string()),
+        ("Token user", token_user),
+    ];
+    let bindings = help::bindings(&app.keymap, app.context());
+    let fields: &[Field] = if app.screen == Screen::Main && !app.is_showing_pull_request() {
+        &Field::ALL
+    } else {
+        &[]
+    };
+    let width = account
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .chain(bindings.iter().map(|(keys, _)| keys.chars().count()))
+        .chain(fields.iter().map(|field| field.label().chars().count()))
+        .max()
+        .unwrap_or(0);
+    let heading = |text: &str| {
+        Line::styled(
+            text.to_string(),
+            app.theme.accent.add_modifier(Modifier::BOLD),
+        )
+    };
+    let inner = block.inner(area);
+    let text_width = inner.width.saturating_sub(width as u16 + 2);
+    let row = |name: &str, text: &str| -> Vec<Line<'static>> {
+        wrap(text, text_width)
+            .into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let name = if index == 0 { name } else { "" };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:width$}  ", name, width = width),
+                        app.theme.accent,
+                    ),
+                    Span::raw(line),
+                ])
+            })
+            .collect()
+    };
+
+    let mut lines = vec![heading("Account")];
+    lines.extend(account.iter().flat_map(|(label, value)| row(label, value)));
+    lines.push(Line::from(""));
+    lines.push(heading("Keys"));
+    lines.extend(
+        bindings
+            .iter()
+            .flat_map(|(keys, description)| row(keys, description)),
+    );
+    if !fields.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(heading("Fields"));
+        lines.extend(
+            fields
+                .iter()
+                .flat_map(|field| row(field.label(), field.help())),
+        );
+    }
+    regions.help_scroll_max = lines.len().saturating_sub(inner.height as usize) as u16;
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((app.help_scroll.min(regions.help_scroll_max), 0)),
+        area,
+    );
+}
+
+/// The mode line with the hints for the current screen, generated from the
+/// keymap so rebound keys show up as they are configured.
+fn instructions(app: &App) -> String {
+    let (mode, hints) = mode_hints(app);
+    format!("[{}] \n {}", mode, keymap::instructions(hints))
+}
+
+/// The name of the current mode and its hints.
+fn mode_hints(app: &App) -> (&'static str, Vec<Option<String>>) {
+    let keymap = &app.keymap;
+    let hint = |actions: &[Action], description: &str| keymap.hint(actions, description);
+    let select = hint(&[Action::SelectPrevious, Action::SelectNext], "select");
+    let help = hint(&[Action::ShowHelp], "show all keys");
+    let typing_help = keymap.typing_hint(&[Action::ShowHelp], "show all keys");
+    let (mode, mut hints) = match app.input_mode {
+        _ if app.screen == Screen::Messages => (
+            "Messages",
+            vec![
+                hint(&[Action::SelectPrevious, Action::SelectNext], "scroll"),
+                hint(
+                    &[Action::ScrollMessagesUp, Action::ScrollMessagesDown],
+                    "scroll a page",
+                ),
+                hint(&[Action::CopyLastError], "copy the last error"),
+                hint(&[Action::Quit], "go back"),
+            ],
+        ),
+        _ if app.screen == Screen::Dashboard => (
+            "Dashboard",
+            vec![
+                select,
+                hint(&[Action::Open], "open the pull request"),
+                hint(&[Action::OpenInBrowser], "view in browser"),
+                hint(&[Action::Sync], "refresh"),
+                hint(&[Action::Quit], "go back"),
+            ],
+        ),
+        _ if app.screen == Screen::Timeline && app.composing_reply => (
+            "Reply",
+            vec![
+                hint(&[Action::SendReply], "send the reply"),
+                hint(&[Action::CancelReply], "cancel"),
+            ],
+        ),
+        _ if app.screen == Screen::Timeline => (
+            "Timeline",
+            vec![
+                select,
+                hint(&[Action::Reply], "reply"),
+                hint(&Action::REACTIONS, "react 👍 👎 😄 😕 ❤️ 🎉 🚀 👀"),
+                hint(&[Action::ResolveThread], "resolve or unresolve a thread"),
+                hint(&[Action::OpenInBrowser], "view in browser"),
+                hint(&[Action::Sync], "refresh"),
+                hint(&[Action::Quit], "go back"),
+            ],
+        ),
+        _ if app.screen == Screen::Notifications => (
+            "Inbox",
+            vec![
+                select,
+                hint(&[Action::Open], "open the pull request"),
+                hint(&[Action::MarkRead], "mark as read"),
+                hint(&[Action::OpenInBrowser], "view in browser"),
+                hint(&[Action::Sync], "refresh"),
+                hint(&[Action::Quit], "go back"),
+            ],
+        ),
+        InputMode::Normal if app.is_showing_pull_request() => (
+            "Normal mode",
+            vec![
+                hint(&[Action::Edit], "edit PR"),
+                hint(&[Action::UpdateBranch], "update branch"),
+                hint(
+                    &[Action::SelectPrevious, Action::SelectNext],
+                    "select a check",
+                ),
+                hint(&[Action::Open], "show its log"),
+                hint(&[Action::RerunChecks], "re-run failed checks"),
+                hint(&[Action::ShowTimeline], "show the timeline"),
+                hint(&[Action::OpenInBrowser], "view in browser"),
+                hint(&[Action::CopyUrl], "copy URL"),
+                hint(&[Action::NewPullRequest], "create PR"),
+                hint(&[Action::Sync], "sync with GitHub"),
+                hint(
+                    &[Action::OpenInbox, Action::OpenDashboard],
+                    "open the inbox/dashboard",
+                ),
+                hint(&[Action::ChooseRepository], "choose repository"),
+                hint(&[Action::ShowMessages], "show the messages"),
+                hint(&[Action::CommandLine], "run a command"),
+                hint(&[Action::CommandPalette], "search the actions"),
+                hint(&[Action::Quit], "quit"),
+            ],
+        ),
+        InputMode::Normal => {
+            let editing = !app.form.is_blank();
+            (
+                "Normal mode",
+                vec![
+                    hint(&[Action::Sync], "sync with GitHub"),
+                    hint(&[Action::NewPullRequest], "create PR"),
+                    editing.then(|| hint(&[Action::Edit], "edit PR")).flatten(),
+                    editing
+                        .then(|| hint(&[Action::TogglePreview], "toggle the preview"))
+                        .flatten(),
+                    hint(&[Action::OpenInbox], "open the inbox"),
+                    hint(&[Action::ChooseRepository], "choose repository"),
+                    hint(
+                        &[
+                            Action::OpenInBrowser,
+                            Action::OpenRepository,
+                            Action::OpenCompare,
+                        ],
+                        "open PR/repo/compare",
+                    ),
+                    hint(
+                        &[Action::CopyUrl, Action::CopyRepositoryUrl],
+                        "copy PR/repo URL",
+                    ),
+                    hint(&[Action::ShowMessages], "show the messages"),
+                    hint(&[Action::CommandLine], "run a command"),
+                    hint(&[Action::CommandPalette], "search the actions"),
+                    hint(&[Action::Quit], "quit"),
+                ],
+            )
+        }
+        InputMode::Editing => (
+            "Editing mode",
+            vec![
+                hint(
+                    &[Action::NextField, Action::PreviousField],
+                    "move to next or previous field",
+                ),
+                hint(
+                    &[Action::OpenEditor],
+                    "write the title or description in $EDITOR",
+                ),
+                hint(&[Action::Submit], "send"),
+                hint(&[Action::StopEditing], "back"),
+            ],
+        ),
+        InputMode::Creating => (
+            "Confirm mode",
+            vec![
+                hint(&[Action::Confirm], "confirm"),
+                hint(&[Action::ContinueEditing], "continue editing"),
+                hint(&[Action::Cancel], "cancel"),
+            ],
+        ),
+    };
+    // First, so it is never the hint that gets cut off.
+    let typing = app.input_mode == InputMode::Editing || app.composing_reply;
+    hints.insert(0, if typing { typing_help } else { help });
+    (mode, hints)
+}
+
+fn render_form(f: &mut Frame, app: &App, area: Rect, regions: &mut Regions) {
+    let area = if app.show_preview {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        render_preview(f, app, chunks[1]);
+        chunks[0]
+    } else {
+        area
+    };
+    let description_lines = app.form.description_lines();
+    // On short terminals the description gives up lines before the other
+    // fields do.
+    let vertical_margin = if area.height < 12 { 1 } else { 2 };
+    let description_height = (description_lines.min(20) + 3)
+        .min(area.height.saturating_sub(2 * vertical_margin + 3) as usize)
+        .max(1);
     let form_layout = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
-        .vertical_margin(2)
+        .vertical_margin(vertical_margin)
         .horizontal_margin(2)
         .constraints(
             [
@@ -73,182 +725,759 @@ pub fn ui(f: &mut Frame, app: &App) {
             ]
             .as_ref(),
         )
-        .split(chunks[1]);
+        .split(area);
+    let title = match app.editing_pull_request {
+        Some(number) => format!("Edit #{}", number),
+        None => "Create".to_string(),
+    };
     let form_block = Block::default()
-        .title("Create")
+        .title(title)
         .padding(Padding::proportional(1))
-        .borders(Borders::ALL);
-    f.render_widget(form_block, chunks[1]);
-    let fields = vec![
-        ("Title", &app.pull_request.title),
-        ("Description", &app.pull_request.description),
-        ("Source Branch", &app.pull_request.source_branch),
-        ("Target Branch", &app.pull_request.target_branch),
-    ];
-
-    for (i, (name, value)) in fields.iter().enumerate() {
-        let (text, style) = match app.input_mode {
-            InputMode::Normal => (
-                format!("{}: {}", name, if value.is_empty() { "" } else { value }),
-                if i == app.current_field {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
-                },
-            ),
-            InputMode::Editing => (
-                format!("{}: {}", name, value),
-                if i == app.current_field {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default()
-                },
-            ),
-            InputMode::Creating => (format!("{}: {}", name, value), Style::default()),
+        .borders(Borders::ALL)
+        .border_style(app.theme.border);
+    f.render_widget(form_block, area);
+    for field in Field::ALL {
+        let area = form_layout[field.index()];
+        regions.push(area, Target::Field(field));
+        let focused = field.index() == app.current_field;
+        let editing = app.input_mode == InputMode::Editing && focused;
+        let style = match app.input_mode {
+            InputMode::Normal if focused => app.theme.focused,
+            InputMode::Editing if focused => app.theme.editing,
+            _ => Style::default(),
         };
-        let mut description_text = app.description_text_area.clone();
-        description_text.set_cursor_style(Style::default().fg(Color::Red));
-        if app.input_mode == InputMode::Normal && i == app.current_field {
-            description_text.set_block(
-                Block::default()
-                    .title("Description")
-                    .style(Style::default().fg(Color::Yellow)),
-            );
-        } else if app.input_mode == InputMode::Editing && i == app.current_field {
-            description_text.set_block(
-                Block::default()
-                    .title("Description")
-                    .style(Style::default().fg(Color::Green)),
-            );
-            description_text.set_cursor_style(
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(ratatui::style::Modifier::REVERSED),
-            );
+        let editing_cursor = app.theme.editing.add_modifier(Modifier::REVERSED);
+
+        if field.is_multiline() {
+            let mut text_area = app.form.input(field).text_area().clone();
+            text_area.set_block(Block::default().title(field.label()).style(style));
+            text_area.set_cursor_style(if editing {
+                editing_cursor
+            } else {
+                app.theme.error
+            });
+            f.render_widget(&text_area, area);
+        } else if editing {
+            let label = format!("{}: ", field.label());
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Length(label.chars().count() as u16),
+                    Constraint::Min(1),
+                ])
+                .split(area);
+            f.render_widget(Paragraph::new(Span::styled(label, style)), chunks[0]);
+            let mut text_area = app.form.input(field).text_area().clone();
+            text_area.set_style(style);
+            text_area.set_cursor_line_style(Style::default());
+            text_area.set_cursor_style(editing_cursor);
+            f.render_widget(&text_area, chunks[1]);
         } else {
-            description_text.set_block(
-                Block::default()
-                    .title("Description")
-                    .style(Style::default()),
-            );
+            let text = format!("{}: {}", field.label(), app.form.value(field));
+            f.render_widget(Paragraph::new(Span::styled(text, style)), area);
         }
+    }
+}
 
-        if i == 1 {
-            f.render_widget(&description_text, form_layout[i]);
-        } else {
-            let paragraph = Paragraph::new(Span::styled(text, style));
-            f.render_widget(paragraph, form_layout[i]);
+/// The description as GitHub will render it, followed by where its links
+/// and references point.
+fn render_preview(f: &mut Frame, app: &App, area: Rect) {
+    let description = app.form.value(Field::Description);
+    let repository_url = app.has_repository().then(|| app.repository_url());
+    let mut lines = vec![
+        Line::styled(
+            app.form.value(Field::Title),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Line::from(""),
+    ];
+    lines.extend(render_markdown(
+        &description,
+        repository_url.as_deref(),
+        &app.theme,
+    ));
+
+    let links = links(&description, repository_url.as_deref());
+    if !links.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::styled("Links", app.theme.muted));
+        for (label, url) in links {
+            lines.push(Line::from(vec![
+                Span::raw(format!("{} → ", label)),
+                Span::styled(url, app.theme.accent),
+            ]));
         }
     }
 
-    render_message(f, "", Color::default(), chunks[2]);
+    let preview = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title("Preview")
+                .padding(Padding::horizontal(1))
+                .borders(Borders::ALL)
+                .border_style(app.theme.border),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(preview, area);
+}
+
+fn render_pull_request(f: &mut Frame, app: &App, area: Rect) {
+    let Some(ref pr) = app.current_pull_request else {
+        return;
+    };
+    let summary = PullRequestSummary::from(pr);
+    let mergeable = match summary.mergeable {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "unknown",
+    };
+    let (checks, checks_style, review, review_style) = match app.pull_request_status {
+        Some(ref status) => (
+            status.checks.label(),
+            checks_style(&app.theme, status.checks),
+            status.review.label(),
+            review_style(&app.theme, status.review),
+        ),
+        None => ("unknown", Style::default(), "unknown", Style::default()),
+    };
+
+    let text = vec![
+        Line::from(Span::styled(
+            format!("#{} {}", summary.number, summary.title),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(format!(
+            "State: {}{}",
+            summary.state,
+            if summary.draft { " (draft)" } else { "" }
+        )),
+        Line::from(format!("Author: @{}", summary.author)),
+        Line::from(format!(
+            "Branches: {} -> {}",
+            summary.source_branch, summary.target_branch
+        )),
+        Line::from(format!("Mergeable: {}", mergeable)),
+        Line::from(vec![
+            Span::raw("Checks: "),
+            Span::styled(checks, checks_style),
+        ]),
+        Line::from(vec![
+            Span::raw("Review: "),
+            Span::styled(review, review_style),
+        ]),
+        Line::from(format!("URL: {}", summary.url)),
+    ];
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title("Pull Request")
+            .padding(Padding::proportional(1))
+            .borders(Borders::ALL)
+            .border_style(app.theme.border),
+    );
+    if app.checks().is_empty() {
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    f.render_widget(paragraph, columns[0]);
+    render_checks(f, app, columns[1]);
+}
 
-    if let Some(ref error_message) = app.error_message {
-        render_message(f, error_message, Color::Red, chunks[2]);
+fn checks_style(theme: &Theme, checks: ChecksState) -> Style {
+    match checks {
+        ChecksState::Success => theme.success,
+        ChecksState::Failure => theme.error,
+        ChecksState::Pending => theme.warning,
+        ChecksState::None => Style::default(),
     }
-    if let Some(ref success_message) = app.success_message {
-        render_message(f, success_message, Color::default(), chunks[2]);
+}
+
+fn review_style(theme: &Theme, review: ReviewDecision) -> Style {
+    match review {
+        ReviewDecision::Approved => theme.success,
+        ReviewDecision::ChangesRequested => theme.error,
+        ReviewDecision::ReviewRequired => theme.warning,
+        ReviewDecision::None => Style::default(),
     }
+}
 
-    // Instructions
-    let instructions = match app.input_mode {
-        InputMode::Normal => {
-            if app.pull_request.description != "" || app.pull_request.title != "" {
-                "[Normal mode] \n Press [s] to sync with GitHub, [n] to create PR, [e] to edit PR or [Esc] to quit"
-            } else {
-                "[Normal mode] \n Press [s] to sync with GitHub, [n] to create PR or [Esc] to quit"
-            }
+fn render_dashboard(f: &mut Frame, app: &App, area: Rect) {
+    let area = match split_detail(area) {
+        Some((list, detail)) => {
+            render_dashboard_detail(f, app, detail);
+            list
+        }
+        None => area,
+    };
+    let now = Utc::now();
+    let mut items = Vec::new();
+    let mut selected = None;
+    let mut repository = None;
+    for (index, entry) in app.dashboard.iter().enumerate() {
+        if repository != Some(&entry.repository) {
+            repository = Some(&entry.repository);
+            items.push(ListItem::new(Line::styled(
+                entry.repository.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+        }
+        if index == app.selected_dashboard_entry {
+            selected = Some(items.len());
         }
-        InputMode::Editing => "[Editing mode] \n Press [Tab]/[BackTab] to move to next or previous field, [Enter] to send or [Esc] to back",
-        InputMode::Creating => {
-            "[Confirm mode] \n Press [Enter] to confirm, Press [e] to continue editing, Press [Esc] to cancel"
+        let involvement: Vec<String> = entry.involvement.iter().map(|i| i.label()).collect();
+        let mut spans = vec![
+            Span::raw(format!(
+                "  #{} {}{} ",
+                entry.number,
+                entry.title,
+                if entry.draft { " (draft)" } else { "" }
+            )),
+            Span::styled(
+                format!(
+                    "@{} · {} · ",
+                    entry.author,
+                    relative_time(entry.created_at, now)
+                ),
+                app.theme.muted,
+            ),
+        ];
+        if entry.status_error.is_some() {
+            spans.push(Span::styled("status unknown", app.theme.muted));
+        } else {
+            spans.extend([
+                Span::styled(entry.checks.label(), checks_style(&app.theme, entry.checks)),
+                Span::raw(" · "),
+                Span::styled(entry.review.label(), review_style(&app.theme, entry.review)),
+            ]);
         }
+        spans.push(Span::styled(
+            format!(" [{}]", involvement.join(", ")),
+            app.theme.muted,
+        ));
+        items.push(ListItem::new(Line::from(spans)));
+    }
+    if items.is_empty() {
+        items.push(ListItem::new(if app.dashboard_loading {
+            "Loading pull requests..."
+        } else {
+            "No open pull requests involve you"
+        }));
+    }
 
+    let title = if app.dashboard_loading && !app.dashboard.is_empty() {
+        "My Pull Requests (refreshing...)"
+    } else {
+        "My Pull Requests"
     };
-    let instructions_paragraph = Paragraph::new(instructions).style(Style::default());
-    f.render_widget(instructions_paragraph, chunks[3]);
-
-    if app.show_confirm_popup {
-        let popup_block = Block::default()
-            .title("Pull Request Confirmation")
-            .borders(Borders::ALL)
-            .style(Style::default());
-
-        let area_confirm_popup = centered_rect(60, 12, f.area());
-        f.render_widget(Clear, area_confirm_popup);
-        f.render_widget(popup_block, area_confirm_popup);
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(app.theme.border),
+        )
+        .highlight_style(app.theme.focused)
+        .highlight_symbol("> ");
+    let mut state = ListState::default().with_selected(selected);
+    f.render_stateful_widget(list, area, &mut state);
+}
 
-        let popup_text = vec![
+/// The selected pull request of the dashboard, next to the list on wide
+/// terminals.
+fn render_dashboard_detail(f: &mut Frame, app: &App, area: Rect) {
+    let mut text = Vec::new();
+    if let Some(entry) = app.selected_dashboard_entry() {
+        let involvement: Vec<String> = entry.involvement.iter().map(|i| i.label()).collect();
+        text = vec![
+            Line::styled(
+                format!("#{} {}", entry.number, entry.title),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::from(""),
+            Line::from(format!("Repository: {}", entry.repository)),
             Line::from(format!(
-                "Please confirm PR creation from {} to {} ",
-                app.pull_request.source_branch, app.pull_request.target_branch
+                "Author: @{}{}",
+                entry.author,
+                if entry.draft { " (draft)" } else { "" }
+            )),
+            Line::from(format!(
+                "Opened: {}",
+                relative_time(entry.created_at, Utc::now())
+            )),
+        ];
+        match entry.status_error {
+            Some(ref error) => text.push(Line::styled(
+                format!("Status unknown: {}", error),
+                app.theme.muted,
             )),
+            None => text.extend([
+                Line::from(vec![
+                    Span::raw("Checks: "),
+                    Span::styled(entry.checks.label(), checks_style(&app.theme, entry.checks)),
+                ]),
+                Line::from(vec![
+                    Span::raw("Review: "),
+                    Span::styled(entry.review.label(), review_style(&app.theme, entry.review)),
+                ]),
+            ]),
+        }
+        text.extend([
+            Line::from(format!("Involvement: {}", involvement.join(", "))),
+            Line::from(format!("URL: {}", entry.url)),
+        ]);
+    }
+    render_detail(f, app, text, area);
+}
+
+/// The selected notification of the inbox, next to the list on wide
+/// terminals.
+fn render_notification_detail(f: &mut Frame, app: &App, area: Rect) {
+    let mut text = Vec::new();
+    if let Some(item) = app.selected_notification() {
+        text = vec![
+            Line::styled(
+                item.title.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
             Line::from(""),
-            Line::from("Press [y] to confirm or [n] to cancel"),
+            Line::from(format!("Repository: {}", item.repository)),
+            Line::from(format!("Reason: {}", item.group.label())),
+            Line::from(format!(
+                "Updated: {}",
+                relative_time(item.updated_at, Utc::now())
+            )),
+            Line::from(format!("URL: {}", item.html_url(&app.web_url()))),
         ];
+    }
+    render_detail(f, app, text, area);
+}
 
-        let popup_paragraph = Paragraph::new(popup_text)
-            .block(Block::default().borders(Borders::NONE))
-            .alignment(ratatui::layout::Alignment::Center);
+fn render_detail(f: &mut Frame, app: &App, text: Vec<Line>, area: Rect) {
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title("Details")
+                .padding(Padding::horizontal(1))
+                .borders(Borders::ALL)
+                .border_style(app.theme.border),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+fn render_timeline(f: &mut Frame, app: &App, area: Rect) {
+    let area = if app.composing_reply {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(8)])
+            .split(area);
+        let title = match app.selected_timeline_entry().map(|entry| &entry.kind) {
+            Some(TimelineKind::ReviewComment { .. }) => "Reply to thread",
+            _ => "Comment",
+        };
+        let mut text_area = app.reply_text_area.clone();
+        text_area.set_block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(app.theme.border),
+        );
+        f.render_widget(&text_area, chunks[1]);
+        chunks[0]
+    } else {
+        area
+    };
+
+    let now = Utc::now();
+    let repository_url = app.repository_url();
+    let items: Vec<ListItem> = app
+        .timeline
+        .iter()
+        .map(|entry| {
+            let indent = match entry.kind {
+                TimelineKind::ReviewComment { reply: true, .. } => "      ",
+                _ => "  ",
+            };
+            let mut header = vec![
+                Span::raw(indent[2..].to_string()),
+                Span::styled(
+                    format!("@{}", entry.author),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {}", entry.action())),
+            ];
+            if let TimelineKind::Commit { .. } = entry.kind {
+                header.push(Span::raw(format!(": {}", entry.body)));
+            }
+            if let Some(created_at) = entry.created_at {
+                header.push(Span::styled(
+                    format!(" · {}", relative_time(created_at, now)),
+                    app.theme.muted,
+                ));
+            }
+            if entry.thread().is_some_and(|thread| thread.resolved) {
+                header.push(Span::styled(" (resolved)", app.theme.success));
+            }
+
+            let mut lines = vec![Line::from(header)];
+            if !matches!(
+                entry.kind,
+                TimelineKind::Event | TimelineKind::Commit { .. }
+            ) {
+                lines.extend(
+                    render_markdown(&entry.body, Some(&repository_url), &app.theme)
+                        .into_iter()
+                        .map(|line| {
+                            let mut spans = vec![Span::raw(indent)];
+                            spans.extend(line.spans);
+                            Line::from(spans).style(line.style)
+                        }),
+                );
+            }
+            if !entry.reactions.is_empty() {
+                let reactions: Vec<String> = entry
+                    .reactions
+                    .iter()
+                    .map(|(content, count)| format!("{} {}", reaction_emoji(content), count))
+                    .collect();
+                lines.push(Line::raw(format!("{}{}", indent, reactions.join("  "))));
+            }
+            ListItem::new(lines)
+        })
+        .collect();
+    let items = if items.is_empty() {
+        vec![ListItem::new(
+            "Nothing has happened on this pull request yet",
+        )]
+    } else {
+        items
+    };
+
+    let title = match app.timeline_pull_request {
+        Some(number) => format!("Timeline #{}", number),
+        None => "Timeline".to_string(),
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(app.theme.border),
+        )
+        .highlight_style(app.theme.focused)
+        .highlight_symbol("> ");
+    let mut state = ListState::default().with_selected(Some(app.selected_timeline_entry));
+    f.render_stateful_widget(list, area, &mut state);
+}
 
-        f.render_widget(popup_paragraph, inner_area(area_confirm_popup));
+fn render_notifications(f: &mut Frame, app: &App, area: Rect) {
+    let area = match split_detail(area) {
+        Some((list, detail)) => {
+            render_notification_detail(f, app, detail);
+            list
+        }
+        None => area,
+    };
+    let now = Utc::now();
+    let mut items = Vec::new();
+    let mut selected = None;
+    let mut group = None;
+    for (index, item) in app.notifications.iter().enumerate() {
+        if group != Some(item.group) {
+            group = Some(item.group);
+            items.push(ListItem::new(Line::styled(
+                item.group.label(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+        }
+        if index == app.selected_notification {
+            selected = Some(items.len());
+        }
+        let reference = match item.pull_request {
+            Some(number) => format!("{}#{}", item.repository, number),
+            None => item.repository.clone(),
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::raw(format!("  {} ", reference)),
+            Span::raw(item.title.clone()),
+            Span::styled(
+                format!(" · {}", relative_time(item.updated_at, now)),
+                app.theme.muted,
+            ),
+        ])));
+    }
+    if items.is_empty() {
+        items.push(ListItem::new("No unread pull request notifications"));
     }
 
-    if app.show_pat_popup {
-        let area = centered_rect(50, 15, f.area());
-        f.render_widget(Clear, area);
-        let mut pat_input_text = app.pat_input.clone();
-        pat_input_text.set_block(
+    let list = List::new(items)
+        .block(
             Block::default()
-                .title("Insert Github PAT")
-                .padding(Padding::new(1, 1, 0, 0))
-                .style(Style::default())
-                .borders(Borders::ALL),
-        );
-        pat_input_text.set_cursor_style(
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(ratatui::style::Modifier::REVERSED),
+                .title("Inbox")
+                .borders(Borders::ALL)
+                .border_style(app.theme.border),
+        )
+        .highlight_style(app.theme.focused)
+        .highlight_symbol("> ");
+    let mut state = ListState::default().with_selected(selected);
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_checks(f: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .checks()
+        .iter()
+        .map(|check| {
+            let style = match check.outcome {
+                CheckOutcome::Success => app.theme.success,
+                CheckOutcome::Failure => app.theme.error,
+                CheckOutcome::Pending => app.theme.warning,
+                _ => Style::default(),
+            };
+            let mut lines = vec![Line::from(vec![
+                Span::styled(check.outcome.symbol(), style),
+                Span::raw(format!(" {} ", check.name)),
+                Span::styled(
+                    format!("{} {}", check.outcome.label(), check.duration_label()),
+                    app.theme.muted,
+                ),
+            ])];
+            if let Some(ref log_tail) = check.log_tail {
+                lines.extend(
+                    log_tail
+                        .iter()
+                        .map(|line| Line::styled(format!("  {}", line), app.theme.muted)),
+                );
+            }
+            ListItem::new(lines)
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("Checks")
+                .borders(Borders::ALL)
+                .border_style(app.theme.border),
+        )
+        .highlight_style(app.theme.focused)
+        .highlight_symbol("> ");
+    let mut state = ListState::default().with_selected(Some(
+        app.selected_check.min(app.checks().len().saturating_sub(1)),
+    ));
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+fn comparison_lines(comparison: &Comparison, width: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::raw(format!(
+            "{} commit(s), {} file(s) changed, ",
+            comparison.total_commits,
+            comparison.files.len()
+        )),
+        Span::styled(format!("+{}", comparison.additions()), theme.success),
+        Span::raw(" "),
+        Span::styled(format!("-{}", comparison.deletions()), theme.error),
+    ])];
+    for warning in comparison.warnings() {
+        lines.push(Line::styled(format!("⚠ {}", warning), theme.warning));
+    }
+    if comparison.files.is_empty() {
+        return lines;
+    }
+    lines.push(Line::from(""));
+
+    // Every row has the same width so the centered block lines up.
+    let bar_width = 20;
+    let name_width = comparison
+        .files
+        .iter()
+        .map(|file| file.filename.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(width.saturating_sub(bar_width + 10).max(10));
+    let max_changes = comparison
+        .files
+        .iter()
+        .map(|file| file.additions + file.deletions)
+        .max()
+        .unwrap_or(0);
+    for file in comparison.files.iter().take(MAX_DIFFSTAT_FILES) {
+        let (plus, minus) = diffstat_bar(
+            file.additions,
+            file.deletions,
+            max_changes,
+            bar_width as u64,
         );
-        pat_input_text.set_placeholder_text("Enter your Github PAT here");
-        pat_input_text.set_mask_char('*');
-        f.render_widget(&pat_input_text, inner_area(area));
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "{:<name_width$} | {:>5} ",
+                truncate_start(&file.filename, name_width),
+                file.additions + file.deletions,
+            )),
+            Span::styled("+".repeat(plus), theme.success),
+            Span::styled("-".repeat(minus), theme.error),
+            Span::raw(" ".repeat(bar_width.saturating_sub(plus + minus))),
+        ]));
+    }
+    if comparison.files.len() > MAX_DIFFSTAT_FILES {
+        lines.push(Line::from(format!(
+            "... and {} more file(s)",
+            comparison.files.len() - MAX_DIFFSTAT_FILES
+        )));
     }
+    lines
+}
 
-    if app.show_exit_popup {
-        let popup_block = Block::default()
-            .title("Exit Confirmation")
-            .borders(Borders::ALL)
-            .style(Style::default());
+fn truncate_start(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count <= width {
+        return text.to_string();
+    }
+    let tail: String = text.chars().skip(count - width + 1).collect();
+    format!("…{}", tail)
+}
 
-        let exit_area = centered_rect(40, 12, f.area());
-        f.render_widget(Clear, exit_area);
-        f.render_widget(popup_block, exit_area);
+/// The newest messages of the log, or older ones once it is scrolled back.
+fn render_output(f: &mut Frame, app: &App, area: Rect, regions: &mut Regions) {
+    let padding = if area.height > 4 {
+        Padding::new(1, 0, 1, 0)
+    } else {
+        Padding::horizontal(1)
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(app.theme.border)
+        .title("Output")
+        .padding(padding);
+    let inner = block.inner(area);
+    let lines = message_lines(app, inner.width);
+    let height = inner.height as usize;
+    let start = message_window(lines.len(), height, app, regions);
+    let newer = lines.len().saturating_sub(start + height);
+    if newer > 0 {
+        block = block.title(format!("Output ({} newer lines)", newer));
+    }
+    let lines: Vec<Line> = lines.into_iter().skip(start).take(height).collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
 
-        let popup_text = vec![
-            Line::from(format!("Are you sure you want to exit?")),
-            Line::from(""),
-            Line::from("Press [y] to confirm or [n] to cancel"),
-        ];
+/// The whole log, scrolled like the Output panel.
+fn render_message_log(f: &mut Frame, app: &App, area: Rect, regions: &mut Regions) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(app.theme.border)
+        .title("Messages")
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    if app.messages.is_empty() {
+        let empty = Paragraph::new(Span::styled("No messages yet", app.theme.muted));
+        f.render_widget(empty.block(block), area);
+        return;
+    }
+    let lines = message_lines(app, inner.width);
+    let height = inner.height as usize;
+    let start = message_window(lines.len(), height, app, regions);
+    let lines: Vec<Line> = lines.into_iter().skip(start).take(height).collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
 
-        let popup_paragraph = Paragraph::new(popup_text)
-            .block(Block::default().borders(Borders::NONE))
-            .alignment(ratatui::layout::Alignment::Center);
+/// The first of `count` wrapped message lines shown in `height` rows.
+/// Records how far back the log can scroll, so a long wrapped message can
+/// be scrolled back to its start but no further.
+fn message_window(count: usize, height: usize, app: &App, regions: &mut Regions) -> usize {
+    let max = count.saturating_sub(height);
+    regions.messages_scroll_max = Some(max);
+    max - app.messages.scroll.min(max)
+}
 
-        f.render_widget(popup_paragraph, inner_area(exit_area));
+/// Every message wrapped to `width`, as `12:04:05 ✗ text` with the
+/// following lines lined up under the text.
+fn message_lines(app: &App, width: u16) -> Vec<Line<'static>> {
+    const PREFIX_WIDTH: u16 = 11;
+    let mut lines = Vec::new();
+    for message in app.messages.iter() {
+        let style = severity_style(&app.theme, message.severity);
+        let text: Vec<&str> = message.text.lines().map(str::trim_start).collect();
+        let wrapped = wrap(&text.join("\n"), width.saturating_sub(PREFIX_WIDTH));
+        for (index, line) in wrapped.into_iter().enumerate() {
+            let prefix = if index == 0 {
+                vec![
+                    Span::styled(
+                        format!("{} ", message.time.format("%H:%M:%S")),
+                        app.theme.muted,
+                    ),
+                    Span::styled(format!("{} ", message.severity.symbol()), style),
+                ]
+            } else {
+                vec![Span::raw(" ".repeat(PREFIX_WIDTH as usize))]
+            };
+            lines.push(Line::from(
+                [prefix, vec![Span::styled(line, style)]].concat(),
+            ));
+        }
     }
+    lines
 }
 
-fn render_message(f: &mut Frame, message: &str, color: Color, area: Rect) {
-    let paragraph = Paragraph::new(Span::from(Span::styled(
-        message,
-        Style::default().fg(color),
-    )))
-    .block(
+/// The newest message over the top right corner until it times out.
+fn render_toast(f: &mut Frame, app: &App, area: Rect) {
+    let Some(message) = app.messages.toast(Instant::now()) else {
+        return;
+    };
+    let style = severity_style(&app.theme, message.severity);
+    let width = (area.width / 2)
+        .clamp(20, 50)
+        .min(area.width.saturating_sub(4));
+    let text = format!("{} {}", message.severity.symbol(), message.text);
+    let text: Vec<&str> = text.lines().map(str::trim_start).collect();
+    let lines = wrap(&text.join("\n"), width.saturating_sub(4));
+    let height = (lines.len() as u16 + 2).min(TOAST_MAX_HEIGHT);
+    let toast = Rect::new(
+        area.right().saturating_sub(width + 2),
+        area.y + 1,
+        width,
+        height,
+    );
+    let lines: Vec<Line> = lines
+        .into_iter()
+        .map(|line| Line::styled(line, style))
+        .collect();
+    f.render_widget(Clear, toast);
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(style)
+                .padding(Padding::horizontal(1)),
+        ),
+        toast,
+    );
+}
+
+fn severity_style(theme: &Theme, severity: Severity) -> Style {
+    match severity {
+        Severity::Success => theme.success,
+        Severity::Warning => theme.warning,
+        Severity::Error => theme.error,
+    }
+}
+
+fn render_message(f: &mut Frame, message: &str, style: Style, app: &App, area: Rect) {
+    let paragraph = Paragraph::new(Span::styled(message, style)).block(
         Block::default()
             .borders(Borders::ALL)
+            .border_style(app.theme.border)
             .title("Output")
-            .padding(Padding::new(1, 0, 1, 0)),
+            .padding(if area.height > 4 {
+                Padding::new(1, 0, 1, 0)
+            } else {
+                Padding::horizontal(1)
+            }),
     );
 
     f.render_widget(paragraph, area);
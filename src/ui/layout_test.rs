@@ -0,0 +1,222 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::dashboard::{DashboardEntry, Involvement};
+    use crate::core::field::Field;
+    use crate::core::notifications::{InboxItem, NotificationGroup};
+    use crate::core::pull_request::{ChecksState, ReviewDecision};
+    use crate::core::screen::Screen;
+    use crate::core::theme::{Theme, ThemeName};
+    use crate::ui::test_util::{app, draw, lines, render};
+    use chrono::Utc;
+    use ratatui::buffer::Buffer;
+    use ratatui::style::{Color, Style};
+
+    fn contains(buffer: &Buffer, text: &str) -> bool {
+        lines(buffer).iter().any(|line| line.contains(text))
+    }
+
+    fn has_color(buffer: &Buffer) -> bool {
+        buffer
+            .content
+            .iter()
+            .any(|cell| cell.fg != Color::Reset || cell.bg != Color::Reset)
+    }
+
+    #[test]
+    fn test_themes_color_the_frame() {
+        let mut app = app();
+        app.theme = Theme::named(ThemeName::Dark);
+        app.set_error("Something failed".to_string());
+        assert!(has_color(&render(&app, 100, 40)));
+
+        app.theme = Theme::named(ThemeName::Monochrome);
+        assert!(!has_color(&render(&app, 100, 40)));
+    }
+
+    #[test]
+    fn test_terminal_too_small() {
+        let buffer = render(&app(), 40, 10);
+        assert_eq!(
+            lines(&buffer),
+            [
+                "╭PRT: Pull Request TUI─────────────────╮",
+                "│                                      │",
+                "│                                      │",
+                "│          Terminal too small          │",
+                "│                 40x10                │",
+                "│       Resize to at least 60x20       │",
+                "│                                      │",
+                "│                                      │",
+                "│                                      │",
+                "╰──────────────────────────────────────╯",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_small_terminal_keeps_the_form_visible() {
+        let buffer = render(&app(), 80, 24);
+        let lines = lines(&buffer);
+        assert!(lines[1].contains("djego/prt"), "{:#?}", lines);
+        assert!(!contains(&buffer, "Github Config"));
+        for label in ["Title", "Description", "Source Branch", "Target Branch"] {
+            assert!(
+                contains(&buffer, label),
+                "{} is missing: {:#?}",
+                label,
+                lines
+            );
+        }
+        assert!(contains(&buffer, "[Normal mode]"));
+        assert!(contains(&buffer, "to quit"), "{:#?}", lines);
+    }
+
+    #[test]
+    fn test_large_terminal_shows_the_repository_panel() {
+        let buffer = render(&app(), 120, 40);
+        assert!(contains(&buffer, "Github Config"));
+        assert!(contains(&buffer, "Owner: djego"));
+        assert!(!contains(&buffer, "Details"));
+    }
+
+    #[test]
+    fn test_wide_terminal_shows_details_next_to_lists() {
+        let mut app = app();
+        app.screen = Screen::Notifications;
+        app.notifications = vec![InboxItem {
+            thread_id: "1".to_string(),
+            group: NotificationGroup::Mentioned,
+            repository: "djego/prt".to_string(),
+            title: "Add themes".to_string(),
+            pull_request: Some(7),
+            updated_at: Utc::now(),
+        }];
+        let buffer = render(&app, 200, 50);
+        assert!(contains(&buffer, "Details"));
+        assert!(contains(&buffer, "Reason: Mentioned"));
+
+        let buffer = render(&app, 100, 40);
+        assert!(!contains(&buffer, "Details"));
+        assert!(contains(&buffer, "djego/prt#7 Add themes"));
+    }
+
+    #[test]
+    fn test_dashboard_keeps_entries_whose_status_is_unknown() {
+        let mut app = app();
+        app.screen = Screen::Dashboard;
+        let mut entry = DashboardEntry {
+            repository: "djego/prt".to_string(),
+            number: 7,
+            title: "Add themes".to_string(),
+            author: "djego".to_string(),
+            url: "https://github.com/djego/prt/pull/7".to_string(),
+            draft: false,
+            created_at: Utc::now(),
+            involvement: vec![Involvement::Authored],
+            checks: ChecksState::None,
+            review: ReviewDecision::None,
+            status_error: None,
+        };
+        entry.set_status(Err("Not Found".to_string()));
+        app.dashboard = vec![entry];
+        let buffer = render(&app, 200, 50);
+        assert!(contains(&buffer, "#7 Add themes"));
+        assert!(contains(&buffer, "status unknown"));
+        assert!(contains(&buffer, "Status unknown: Not Found"));
+    }
+
+    #[test]
+    fn test_dashboard_uses_the_muted_style_of_the_theme() {
+        let mut app = app();
+        app.theme.muted = Style::default().fg(Color::Magenta);
+        app.screen = Screen::Dashboard;
+        let entry = |number: u64| DashboardEntry {
+            repository: "djego/prt".to_string(),
+            number,
+            title: "Add themes".to_string(),
+            author: "djego".to_string(),
+            url: format!("https://github.com/djego/prt/pull/{}", number),
+            draft: false,
+            created_at: Utc::now(),
+            involvement: vec![Involvement::Authored],
+            checks: ChecksState::None,
+            review: ReviewDecision::None,
+            status_error: None,
+        };
+        app.dashboard = vec![entry(7), entry(8)];
+        // The second entry, which isn't drawn in the focused style.
+        let buffer = render(&app, 100, 40);
+        let (row, line) = lines(&buffer)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, line)| line.contains("[author]"))
+            .nth(1)
+            .unwrap();
+        let column = line[..line.find("[author]").unwrap()].chars().count();
+        assert_eq!(buffer[(column as u16, row as u16)].fg, Color::Magenta);
+    }
+
+    #[test]
+    fn test_confirm_popup_of_an_edit_asks_to_update() {
+        let mut app = app();
+        app.form.set(Field::Title, "Add themes");
+        app.confirm_pull_request();
+        assert!(contains(
+            &render(&app, 100, 40),
+            "Please confirm PR creation"
+        ));
+
+        app.editing_pull_request = Some(7);
+        let buffer = render(&app, 100, 40);
+        assert!(contains(&buffer, "Please confirm the update of PR #7"));
+        assert!(!contains(&buffer, "creation"));
+    }
+
+    #[test]
+    fn test_help_shows_why_the_token_user_is_unknown() {
+        let mut app = app();
+        app.config_pat = "token".to_string();
+        app.show_help = true;
+        assert!(contains(
+            &render(&app, 100, 40),
+            "unknown until the next sync"
+        ));
+
+        app.token_user = Some(Err("Bad credentials".to_string()));
+        assert!(contains(&render(&app, 100, 40), "unknown, Bad credentials"));
+    }
+
+    #[test]
+    fn test_help_scrolls_to_its_last_wrapped_line() {
+        let mut app = app();
+        app.show_help = true;
+        app.token_user = Some(Err(format!("{} end", "Bad credentials ".repeat(20))));
+        let (_, regions) = draw(&app, 80, 24);
+        assert!(regions.help_scroll_max > 0);
+
+        app.scroll(i16::MAX);
+        app.clamp_help_scroll(regions.help_scroll_max);
+        assert_eq!(app.help_scroll, regions.help_scroll_max);
+        let buffer = render(&app, 80, 24);
+        assert!(contains(&buffer, "Description"));
+
+        app.scroll(-(regions.help_scroll_max as i16));
+        assert!(contains(&render(&app, 80, 24), " end"));
+    }
+
+    #[test]
+    fn test_a_long_wrapped_error_scrolls_back_to_its_start() {
+        let mut app = app();
+        app.set_error(format!("Start {} end", "of a long error ".repeat(40)));
+        app.messages.dismiss();
+        let (buffer, regions) = draw(&app, 80, 24);
+        let max = regions.messages_scroll_max.unwrap();
+        assert!(max > 0);
+        assert!(!contains(&buffer, "Start"));
+
+        app.messages.scroll_up(usize::MAX);
+        app.messages.clamp_scroll(max);
+        assert_eq!(app.messages.scroll, max);
+        assert!(contains(&render(&app, 80, 24), "Start"));
+    }
+}
@@ -0,0 +1,432 @@
+use crate::core::theme::Theme;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+const KEYWORDS: [&str; 44] = [
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "class",
+    "const",
+    "continue",
+    "def",
+    "do",
+    "elif",
+    "else",
+    "enum",
+    "export",
+    "false",
+    "fn",
+    "for",
+    "from",
+    "func",
+    "function",
+    "if",
+    "impl",
+    "import",
+    "in",
+    "interface",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "new",
+    "None",
+    "null",
+    "package",
+    "pub",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "then",
+    "true",
+    "type",
+    "use",
+    "while",
+];
+
+/// Renders the markdown GitHub comments and descriptions commonly use:
+/// headings, lists and checkboxes, quotes, code blocks, tables and inline
+/// emphasis, code and links. `repository_url` is the web URL of the
+/// repository `#123` and `@user` references are resolved against; without
+/// it they are left as written. Colours come from `theme`.
+pub fn render_markdown(
+    text: &str,
+    repository_url: Option<&str>,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut code_language: Option<String> = None;
+    let raw_lines: Vec<&str> = text.lines().collect();
+    let mut index = 0;
+
+    while index < raw_lines.len() {
+        let raw = raw_lines[index];
+        let trimmed = raw.trim_start();
+        index += 1;
+
+        if let Some(fence) = trimmed.strip_prefix("```") {
+            code_language = match code_language {
+                Some(_) => None,
+                None => Some(fence.trim().to_lowercase()),
+            };
+            continue;
+        }
+        if let Some(ref language) = code_language {
+            let mut spans = vec![Span::raw("  ")];
+            spans.extend(highlight_code(raw, language, theme));
+            lines.push(Line::from(spans));
+            continue;
+        }
+
+        if trimmed.starts_with('|') {
+            let mut rows = vec![trimmed];
+            while index < raw_lines.len() && raw_lines[index].trim_start().starts_with('|') {
+                rows.push(raw_lines[index].trim_start());
+                index += 1;
+            }
+            lines.extend(table(&rows, repository_url, theme));
+        } else if let Some((level, heading)) = heading(trimmed) {
+            let mut style = Style::default().add_modifier(Modifier::BOLD);
+            if level == 1 {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            lines.push(Line::styled(heading.to_string(), style));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let mut spans = vec![Span::styled("│ ", theme.muted)];
+            spans.extend(
+                render_inline(quote.trim_start(), repository_url, theme)
+                    .into_iter()
+                    .map(|span| span.patch_style(Style::default().add_modifier(Modifier::ITALIC))),
+            );
+            lines.push(Line::from(spans));
+        } else if let Some((marker, item)) = list_item(trimmed) {
+            let indent = " ".repeat(raw.len() - trimmed.len());
+            let mut spans = vec![Span::raw(indent)];
+            if let Some(task) = item.strip_prefix("[ ] ") {
+                spans.push(Span::raw("☐ "));
+                spans.extend(render_inline(task, repository_url, theme));
+            } else if let Some(task) = item
+                .strip_prefix("[x] ")
+                .or_else(|| item.strip_prefix("[X] "))
+            {
+                spans.push(Span::styled("☑ ", theme.success));
+                spans.extend(
+                    render_inline(task, repository_url, theme)
+                        .into_iter()
+                        .map(|span| span.patch_style(theme.muted)),
+                );
+            } else {
+                spans.push(Span::raw(format!("{} ", marker)));
+                spans.extend(render_inline(item, repository_url, theme));
+            }
+            lines.push(Line::from(spans));
+        } else {
+            lines.push(Line::from(render_inline(raw, repository_url, theme)));
+        }
+    }
+    lines
+}
+
+/// Splits a line into styled spans for `**bold**`, `*italic*`, `` `code` ``,
+/// `[text](url)` and, when `repository_url` is known, `#123` and `@user`.
+pub fn render_inline(
+    text: &str,
+    repository_url: Option<&str>,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let previous = plain.chars().last();
+        // Underscores inside words, as in snake_case, are not emphasis.
+        let in_word = c == '_' && previous.is_some_and(char::is_alphanumeric);
+        let styled = match c {
+            _ if in_word => None,
+            '`' => delimited(rest, "`").map(|(code, len)| (Span::styled(code, theme.code), len)),
+            '*' | '_' if rest.starts_with("**") || rest.starts_with("__") => {
+                delimited(rest, &rest[..2]).map(|(bold, len)| {
+                    (
+                        Span::styled(bold, Style::default().add_modifier(Modifier::BOLD)),
+                        len,
+                    )
+                })
+            }
+            '*' | '_' => delimited(rest, &rest[..1]).map(|(italic, len)| {
+                (
+                    Span::styled(italic, Style::default().add_modifier(Modifier::ITALIC)),
+                    len,
+                )
+            }),
+            '[' => link(rest).map(|(label, _, len)| (Span::styled(label, link_style(theme)), len)),
+            '#' | '@' if repository_url.is_some() => reference(rest, previous)
+                .map(|(label, len)| (Span::styled(label, reference_style(theme)), len)),
+            _ => None,
+        };
+        match styled {
+            Some((span, len)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::raw(std::mem::take(&mut plain)));
+                }
+                spans.push(span);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+    spans
+}
+
+/// The links, issue and user references of `text` with their targets, in
+/// order of appearance and without duplicates. Code is skipped.
+pub fn links(text: &str, repository_url: Option<&str>) -> Vec<(String, String)> {
+    let mut links: Vec<(String, String)> = Vec::new();
+    let mut in_code_block = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        let mut rest = line;
+        let mut previous = None;
+        while let Some(c) = rest.chars().next() {
+            let found = match c {
+                '`' => {
+                    let len = delimited(rest, "`").map_or(1, |(_, len)| len);
+                    rest = &rest[len..];
+                    previous = Some('`');
+                    continue;
+                }
+                '[' => link(rest),
+                '#' | '@' => repository_url.and_then(|repository_url| {
+                    let (label, len) = reference(rest, previous)?;
+                    Some((label.clone(), reference_url(&label, repository_url), len))
+                }),
+                _ => None,
+            };
+            let len = match found {
+                Some((label, url, len)) => {
+                    if !links.iter().any(|(_, existing)| *existing == url) {
+                        links.push((label, url));
+                    }
+                    len
+                }
+                None => c.len_utf8(),
+            };
+            previous = rest[..len].chars().last();
+            rest = &rest[len..];
+        }
+    }
+    links
+}
+
+fn link_style(theme: &Theme) -> Style {
+    theme.accent.add_modifier(Modifier::UNDERLINED)
+}
+
+fn reference_style(theme: &Theme) -> Style {
+    theme.accent.add_modifier(Modifier::BOLD)
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    line[level..]
+        .strip_prefix(' ')
+        .map(|heading| (level, heading.trim()))
+}
+
+/// The marker to show and the text of a bulleted or numbered list item.
+fn list_item(line: &str) -> Option<(String, &str)> {
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return Some(("•".to_string(), item));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let item = line[digits..].strip_prefix(". ")?;
+    Some((format!("{}.", &line[..digits]), item))
+}
+
+/// Text between `marker` at the start of `text` and the next `marker`, with
+/// the number of bytes consumed.
+fn delimited(text: &str, marker: &str) -> Option<(String, usize)> {
+    let inner = &text[marker.len()..];
+    let end = inner.find(marker)?;
+    if end == 0 {
+        return None;
+    }
+    Some((inner[..end].to_string(), marker.len() * 2 + end))
+}
+
+/// The label and target of a `[label](url)` link, with the number of bytes
+/// consumed.
+fn link(text: &str) -> Option<(String, String, usize)> {
+    let close = text.find("](")?;
+    let end = text[close..].find(')')? + close;
+    let label = &text[1..close];
+    if label.contains('[') {
+        return None;
+    }
+    Some((label.to_string(), text[close + 2..end].to_string(), end + 1))
+}
+
+/// A `#123` or `@user` reference at the start of `text`. References only
+/// start a word, so `a@b.c` and `C#1` are left alone.
+fn reference(text: &str, previous: Option<char>) -> Option<(String, usize)> {
+    if previous.is_some_and(|c| c.is_alphanumeric() || c == '/' || c == '`') {
+        return None;
+    }
+    let name = &text[1..];
+    let len = if text.starts_with('#') {
+        name.chars().take_while(char::is_ascii_digit).count()
+    } else {
+        name.chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .count()
+    };
+    if len == 0 {
+        return None;
+    }
+    Some((text[..len + 1].to_string(), len + 1))
+}
+
+fn reference_url(reference: &str, repository_url: &str) -> String {
+    let repository_url = repository_url.trim_end_matches('/');
+    match reference.strip_prefix('#') {
+        Some(number) => format!("{}/issues/{}", repository_url, number),
+        None => {
+            let host = repository_url
+                .rsplitn(3, '/')
+                .nth(2)
+                .unwrap_or(repository_url);
+            format!("{}/{}", host, &reference[1..])
+        }
+    }
+}
+
+/// Colours keywords, strings, numbers and comments of a code block line.
+/// Blocks without a language are shown in the plain code style.
+fn highlight_code(line: &str, language: &str, theme: &Theme) -> Vec<Span<'static>> {
+    if language.is_empty() {
+        return vec![Span::styled(line.to_string(), theme.code)];
+    }
+    let comment = match language {
+        "python" | "py" | "sh" | "bash" | "shell" | "console" | "yaml" | "yml" | "toml"
+        | "ruby" | "rb" => "#",
+        _ => "//",
+    };
+
+    let mut spans = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let (len, style) = if rest.starts_with(comment) {
+            (rest.len(), theme.muted)
+        } else if c == '"' || c == '\'' {
+            let len = rest[1..].find(c).map_or(rest.len(), |end| end + 2);
+            (len, theme.success)
+        } else if c.is_alphanumeric() || c == '_' {
+            let word_len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..word_len];
+            let style = if KEYWORDS.contains(&word) {
+                theme.keyword
+            } else if c.is_ascii_digit() {
+                theme.warning
+            } else {
+                theme.code
+            };
+            (word_len, style)
+        } else {
+            (c.len_utf8(), theme.code)
+        };
+        spans.push(Span::styled(rest[..len].to_string(), style));
+        rest = &rest[len..];
+    }
+    spans
+}
+
+/// Lays out a pipe table with aligned columns, the header in bold and the
+/// delimiter row drawn as a rule.
+fn table(rows: &[&str], repository_url: Option<&str>, theme: &Theme) -> Vec<Line<'static>> {
+    let cells: Vec<Vec<Vec<Span<'static>>>> = rows
+        .iter()
+        .filter(|row| !is_delimiter_row(row))
+        .map(|row| {
+            let row = row.trim().trim_start_matches('|');
+            let row = row.strip_suffix('|').unwrap_or(row);
+            row.split('|')
+                .map(|cell| render_inline(cell.trim(), repository_url, theme))
+                .collect()
+        })
+        .collect();
+    let width = |cell: &Vec<Span>| cell.iter().map(|span| span.width()).sum::<usize>();
+    let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            cells
+                .iter()
+                .filter_map(|row| row.get(column))
+                .map(width)
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let has_header = rows.get(1).is_some_and(|row| is_delimiter_row(row));
+
+    let mut lines = Vec::new();
+    for (index, row) in cells.into_iter().enumerate() {
+        let mut spans = Vec::new();
+        for (column, column_width) in widths.iter().enumerate() {
+            if column > 0 {
+                spans.push(Span::styled(" │ ", theme.muted));
+            }
+            let cell = row.get(column).cloned().unwrap_or_default();
+            let padding = column_width - width(&cell);
+            if index == 0 && has_header {
+                spans.extend(
+                    cell.into_iter().map(|span| {
+                        span.patch_style(Style::default().add_modifier(Modifier::BOLD))
+                    }),
+                );
+            } else {
+                spans.extend(cell);
+            }
+            spans.push(Span::raw(" ".repeat(padding)));
+        }
+        lines.push(Line::from(spans));
+        if index == 0 && has_header {
+            let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+            lines.push(Line::styled(rule.join("─┼─"), theme.muted));
+        }
+    }
+    lines
+}
+
+fn is_delimiter_row(row: &str) -> bool {
+    let row = row.trim();
+    row.contains('-') && row.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::theme::{Theme, ThemeName};
+    use crate::ui::markdown::{links, render_inline, render_markdown};
+    use ratatui::style::{Color, Modifier};
+
+    const REPOSITORY: Option<&str> = Some("https://github.com/djego/prt");
+
+    fn text(line: &ratatui::text::Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_inline() {
+        let spans = render_inline(
+            "Use **bold**, `code` and [docs](https://example.com)",
+            None,
+            &Theme::default(),
+        );
+        let contents: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(contents, ["Use ", "bold", ", ", "code", " and ", "docs"]);
+        assert!(spans[1].style.add_modifier.contains(Modifier::BOLD));
+        assert!(spans[5].style.add_modifier.contains(Modifier::UNDERLINED));
+
+        let spans = render_inline(
+            "snake_case_name stays *plain* 2 * 3",
+            None,
+            &Theme::default(),
+        );
+        let contents: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(contents, ["snake_case_name stays ", "plain", " 2 * 3"]);
+    }
+
+    #[test]
+    fn test_render_inline_references() {
+        let spans = render_inline(
+            "Fixes #12, thanks @alice (me@example.com)",
+            REPOSITORY,
+            &Theme::default(),
+        );
+        let contents: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(
+            contents,
+            ["Fixes ", "#12", ", thanks ", "@alice", " (me@example.com)"]
+        );
+        assert_eq!(spans[1].style.fg, Theme::default().accent.fg);
+
+        let spans = render_inline("Fixes #12", None, &Theme::default());
+        assert_eq!(spans.len(), 1);
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let lines = render_markdown(
+            "# Title\n\n- one\n  - two\n> quoted\n```\nlet x = 1;\n```",
+            None,
+            &Theme::default(),
+        );
+        let texts: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(
+            texts,
+            ["Title", "", "• one", "  • two", "│ quoted", "  let x = 1;"]
+        );
+        assert!(lines[0].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_render_markdown_tasks_and_numbered_lists() {
+        let lines = render_markdown(
+            "- [ ] todo\n- [x] done\n1. first\n10. tenth",
+            None,
+            &Theme::default(),
+        );
+        let texts: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(texts, ["☐ todo", "☑ done", "1. first", "10. tenth"]);
+    }
+
+    #[test]
+    fn test_render_markdown_highlights_code() {
+        let lines = render_markdown(
+            "```rust\nlet name = \"prt\"; // note\n```",
+            None,
+            &Theme::default(),
+        );
+        assert_eq!(lines.len(), 1);
+        let keyword = &lines[0].spans[1];
+        assert_eq!(keyword.content, "let");
+        assert_eq!(keyword.style.fg, Some(Color::Magenta));
+        let string = lines[0]
+            .spans
+            .iter()
+            .find(|span| span.content == "\"prt\"")
+            .unwrap();
+        assert_eq!(string.style.fg, Some(Color::Green));
+        assert_eq!(lines[0].spans.last().unwrap().content, "// note");
+    }
+
+    #[test]
+    fn test_render_markdown_tables() {
+        let lines = render_markdown(
+            "| Name | Value |\n|---|:---:|\n| a | **long value** |",
+            None,
+            &Theme::default(),
+        );
+        let texts: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(
+            texts,
+            [
+                "Name │ Value     ",
+                "─────┼───────────",
+                "a    │ long value"
+            ]
+        );
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_links() {
+        let links = links(
+            "See [docs](https://example.com) and #3, cc @bob\n`#4` is code\n```\n#5\n```\nAgain #3",
+            REPOSITORY,
+        );
+        assert_eq!(
+            links,
+            [
+                ("docs".to_string(), "https://example.com".to_string()),
+                (
+                    "#3".to_string(),
+                    "https://github.com/djego/prt/issues/3".to_string()
+                ),
+                ("@bob".to_string(), "https://github.com/bob".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_follows_the_theme() {
+        let text = "> quote\n- [x] done\n```rust\nlet n = 1; // note\n```\n`code` [link](https://example.com)\n| a | b |";
+        let monochrome = Theme::named(ThemeName::Monochrome);
+        let lines = render_markdown(text, REPOSITORY, &monochrome);
+        for span in lines.iter().flat_map(|line| &line.spans) {
+            assert_eq!(span.style.fg, None, "{:?}", span);
+            assert_eq!(span.style.bg, None, "{:?}", span);
+        }
+
+        let light = Theme::named(ThemeName::Light);
+        let lines = render_markdown("`code`", None, &light);
+        assert_eq!(lines[0].spans[0].style, light.code);
+    }
+}
@@ -1,2 +1,13 @@
 pub mod layout;
+pub mod layout_test;
+pub mod markdown;
+pub mod markdown_test;
+pub mod mouse;
+pub mod mouse_test;
+pub mod responsive;
+pub mod responsive_test;
+pub mod snapshot_test;
+pub mod terminal;
+#[cfg(test)]
+pub mod test_util;
 pub mod util;
@@ -0,0 +1,43 @@
+use crate::core::field::Field;
+use crate::core::keymap::Action;
+use ratatui::layout::{Position, Rect};
+
+/// What a click at some position of the last frame lands on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// A form field, which starts editing it.
+    Field(Field),
+    /// A button of the confirmation popup, which runs the action of its key.
+    Button(Action),
+    /// A button of the exit popup: exit, or stay.
+    Exit(bool),
+    /// Anywhere but the buttons of an open popup: popups are modal, so
+    /// clicks don't reach what is behind them.
+    Popup,
+}
+
+/// The clickable areas of the last frame, recorded while it is drawn.
+/// Later areas are drawn on top of earlier ones and win.
+#[derive(Debug, Clone, Default)]
+pub struct Regions {
+    areas: Vec<(Rect, Target)>,
+    /// How far the help overlay could scroll with its lines wrapped to the
+    /// last frame.
+    pub help_scroll_max: u16,
+    /// How far the message log could scroll, when it was shown.
+    pub messages_scroll_max: Option<usize>,
+}
+
+impl Regions {
+    pub fn push(&mut self, area: Rect, target: Target) {
+        self.areas.push((area, target));
+    }
+
+    pub fn target_at(&self, column: u16, row: u16) -> Option<Target> {
+        self.areas
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, target)| *target)
+    }
+}
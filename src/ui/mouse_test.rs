@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::field::Field;
+    use crate::core::keymap::Action;
+    use crate::ui::mouse::{Regions, Target};
+    use crate::ui::test_util::{app, draw};
+    use ratatui::layout::Rect;
+
+    fn regions(app: &App) -> Regions {
+        draw(app, 100, 40).1
+    }
+
+    fn targets(regions: &Regions) -> Vec<Target> {
+        let mut targets = Vec::new();
+        for row in 0..40 {
+            for column in 0..100 {
+                if let Some(target) = regions.target_at(column, row) {
+                    if !targets.contains(&target) {
+                        targets.push(target);
+                    }
+                }
+            }
+        }
+        targets
+    }
+
+    #[test]
+    fn test_target_at() {
+        let mut regions = Regions::default();
+        regions.push(Rect::new(0, 0, 10, 10), Target::Field(Field::Title));
+        regions.push(Rect::new(2, 2, 4, 1), Target::Popup);
+        assert_eq!(regions.target_at(0, 0), Some(Target::Field(Field::Title)));
+        assert_eq!(regions.target_at(3, 2), Some(Target::Popup));
+        assert_eq!(regions.target_at(10, 0), None);
+    }
+
+    #[test]
+    fn test_form_fields_are_clickable() {
+        let app = app();
+        let targets = targets(&regions(&app));
+        for field in Field::ALL {
+            assert!(targets.contains(&Target::Field(field)), "{:?}", field);
+        }
+    }
+
+    #[test]
+    fn test_popups_cover_the_form() {
+        let mut app = app();
+        app.confirm_pull_request();
+        let buttons = targets(&regions(&app));
+        assert!(buttons.contains(&Target::Button(Action::Confirm)));
+        assert!(buttons.contains(&Target::Button(Action::ContinueEditing)));
+        assert!(buttons.contains(&Target::Button(Action::Cancel)));
+
+        app.show_confirm_popup = false;
+        app.show_exit_popup = true;
+        let regions = regions(&app);
+        assert_eq!(regions.target_at(50, 20), Some(Target::Popup));
+        let buttons = targets(&regions);
+        assert!(buttons.contains(&Target::Exit(true)));
+        assert!(buttons.contains(&Target::Exit(false)));
+    }
+
+    #[test]
+    fn test_clicks_outside_a_popup_do_not_reach_the_form() {
+        let mut app = app();
+        let regions_before = regions(&app);
+        let title = (0..40)
+            .flat_map(|row| (0..100).map(move |column| (column, row)))
+            .find(|&(column, row)| {
+                regions_before.target_at(column, row) == Some(Target::Field(Field::Title))
+            })
+            .unwrap();
+
+        app.confirm_pull_request();
+        let regions = regions(&app);
+        assert_eq!(regions.target_at(title.0, title.1), Some(Target::Popup));
+        assert_eq!(regions.target_at(0, 0), Some(Target::Popup));
+    }
+}
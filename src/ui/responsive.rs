@@ -0,0 +1,125 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+/// Below this size the screen only says that the terminal is too small.
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 20;
+/// Below this height the repository panel collapses into one header line.
+pub const COMPACT_HEIGHT: u16 = 30;
+/// From this width a list gets a detail view next to it.
+pub const DETAIL_WIDTH: u16 = 130;
+
+/// The most lines the instructions take before they are cut off.
+pub const MAX_INSTRUCTION_LINES: u16 = 5;
+
+/// Where the parts of the main screen go for a terminal of some size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenLayout {
+    /// The repository panel, or a single line when `compact`.
+    pub header: Rect,
+    pub body: Rect,
+    pub message: Rect,
+    pub instructions: Rect,
+    pub compact: bool,
+}
+
+impl ScreenLayout {
+    /// `None` when the terminal is smaller than `MIN_WIDTH` x `MIN_HEIGHT`.
+    /// The instructions get as many lines as they need once wrapped, up to
+    /// `MAX_INSTRUCTION_LINES`.
+    pub fn new(area: Rect, instructions: &str) -> Option<ScreenLayout> {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            return None;
+        }
+        let compact = area.height < COMPACT_HEIGHT;
+        let (margin, header, message) = if compact { (1, 1, 4) } else { (2, 7, 6) };
+        let instructions =
+            wrapped_lines(instructions, area.width - 2 * margin).clamp(1, MAX_INSTRUCTION_LINES);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(margin)
+            .constraints([
+                Constraint::Length(header),
+                Constraint::Min(0),
+                Constraint::Length(message),
+                Constraint::Length(instructions),
+            ])
+            .split(area);
+        Some(ScreenLayout {
+            header: chunks[0],
+            body: chunks[1],
+            message: chunks[2],
+            instructions: chunks[3],
+            compact,
+        })
+    }
+}
+
+/// How many lines `text` takes when wrapped at word boundaries to `width`.
+pub fn wrapped_lines(text: &str, width: u16) -> u16 {
+    wrap(text, width).len() as u16
+}
+
+/// `text` wrapped at word boundaries to `width`, breaking words that are
+/// longer than a line.
+pub fn wrap(text: &str, width: u16) -> Vec<String> {
+    let width = width.max(1) as usize;
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let mut current = String::new();
+        let mut column = 0;
+        for word in line.split(' ') {
+            let length = word.chars().count();
+            if column > 0 && column + 1 + length <= width {
+                current.push(' ');
+                column += 1;
+            } else if column > 0 {
+                lines.push(std::mem::take(&mut current));
+                column = 0;
+            }
+            for c in word.chars() {
+                if column == width {
+                    lines.push(std::mem::take(&mut current));
+                    column = 0;
+                }
+                current.push(c);
+                column += 1;
+            }
+        }
+        lines.push(current);
+    }
+    lines
+}
+
+/// Splits a list from its detail view on wide terminals.
+pub fn split_detail(area: Rect) -> Option<(Rect, Rect)> {
+    if area.width < DETAIL_WIDTH {
+        return None;
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area);
+    Some((chunks[0], chunks[1]))
+}
+
+/// A centered popup taking a share of `area`, but never smaller than
+/// `min_width` x `min_height` unless the area itself is.
+pub fn popup_rect(
+    area: Rect,
+    percent_x: u16,
+    percent_y: u16,
+    min_width: u16,
+    min_height: u16,
+) -> Rect {
+    let share = |length: u16, percent: u16| (length as u32 * percent as u32 / 100) as u16;
+    let width = share(area.width, percent_x).max(min_width).min(area.width);
+    let height = share(area.height, percent_y)
+        .max(min_height)
+        .min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use crate::ui::responsive::{popup_rect, split_detail, wrap, wrapped_lines, ScreenLayout};
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_screen_layout() {
+        assert_eq!(ScreenLayout::new(Rect::new(0, 0, 59, 24), ""), None);
+        assert_eq!(ScreenLayout::new(Rect::new(0, 0, 80, 15), ""), None);
+
+        let small = ScreenLayout::new(Rect::new(0, 0, 80, 24), "one\ntwo\nthree").unwrap();
+        assert!(small.compact);
+        assert_eq!(small.header, Rect::new(1, 1, 78, 1));
+        assert_eq!(small.instructions, Rect::new(1, 20, 78, 3));
+        assert_eq!(small.body.height, 14);
+
+        let large = ScreenLayout::new(Rect::new(0, 0, 200, 50), "one").unwrap();
+        assert!(!large.compact);
+        assert_eq!(large.header.height, 7);
+        assert_eq!(large.instructions.height, 1);
+        assert_eq!(large.body.height, 32);
+    }
+
+    #[test]
+    fn test_wrapped_lines() {
+        assert_eq!(wrapped_lines("", 10), 0);
+        assert_eq!(wrapped_lines("one two", 10), 1);
+        assert_eq!(wrapped_lines("one two three", 10), 2);
+        assert_eq!(wrapped_lines("[Mode] \n Press [a] to go", 10), 3);
+        assert_eq!(wrapped_lines("abcdefghijklmnopqrstuvwxy", 10), 3);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("one two three", 10), ["one two", "three"]);
+        assert_eq!(wrap("abcdefghijkl", 5), ["abcde", "fghij", "kl"]);
+        assert_eq!(wrap("one\n\ntwo", 10), ["one", "", "two"]);
+    }
+
+    #[test]
+    fn test_split_detail() {
+        assert_eq!(split_detail(Rect::new(0, 0, 76, 20)), None);
+        let (list, detail) = split_detail(Rect::new(0, 0, 200, 20)).unwrap();
+        assert_eq!(list.width + detail.width, 200);
+        assert!(list.width > detail.width);
+    }
+
+    #[test]
+    fn test_popup_rect() {
+        let area = Rect::new(0, 0, 200, 50);
+        assert_eq!(popup_rect(area, 50, 20, 40, 5), Rect::new(50, 20, 100, 10));
+        let area = Rect::new(0, 0, 60, 16);
+        assert_eq!(popup_rect(area, 40, 12, 36, 5), Rect::new(12, 5, 36, 5));
+        assert_eq!(popup_rect(area, 40, 12, 80, 20), area);
+    }
+}
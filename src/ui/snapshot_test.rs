@@ -0,0 +1,236 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::field::Field;
+    use crate::core::input_mode::InputMode;
+    use crate::core::message_log::{Message, Severity};
+    use crate::core::screen::Screen;
+    use crate::core::theme::{Theme, ThemeName};
+    use crate::ui::test_util::{app, render};
+    use chrono::{Local, TimeZone};
+    use ratatui::buffer::{Buffer, Cell};
+    use ratatui::style::Color;
+    use std::path::PathBuf;
+    use std::{env, fs};
+
+    /// Small, common and large terminals.
+    const SIZES: [(u16, u16); 3] = [(60, 20), (80, 24), (120, 40)];
+
+    fn filled_app() -> App {
+        let mut app = app();
+        app.form.set(Field::Title, "Add snapshot tests");
+        app.form.set(
+            Field::Description,
+            "Renders every mode and popup.\n\n- at several sizes\n- against stored snapshots",
+        );
+        app
+    }
+
+    /// A message logged at a fixed time of day.
+    fn message(severity: Severity, text: &str, minute: u32) -> Message {
+        Message {
+            time: Local.with_ymd_and_hms(2026, 10, 18, 9, minute, 0).unwrap(),
+            ..Message::new(severity, text)
+        }
+    }
+
+    /// The symbols of the buffer, one line per row with trailing spaces cut.
+    fn text(buffer: &Buffer) -> String {
+        let width = buffer.area.width as usize;
+        let mut text = String::new();
+        for row in buffer.content.chunks(width) {
+            let line: String = row.iter().map(|cell| cell.symbol()).collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The styled runs of the buffer, one per line as `row start-end` and
+    /// the colours and modifiers that differ from the terminal's defaults.
+    fn styles(buffer: &Buffer) -> String {
+        let width = buffer.area.width as usize;
+        let mut styles = String::new();
+        for (y, row) in buffer.content.chunks(width).enumerate() {
+            let mut start = 0;
+            while start < row.len() {
+                let style = |cell: &Cell| (cell.fg, cell.bg, cell.modifier);
+                let end = start
+                    + row[start..]
+                        .iter()
+                        .take_while(|cell| style(cell) == style(&row[start]))
+                        .count();
+                let (fg, bg, modifier) = style(&row[start]);
+                let mut parts = Vec::new();
+                if fg != Color::Reset {
+                    parts.push(format!("fg={:?}", fg));
+                }
+                if bg != Color::Reset {
+                    parts.push(format!("bg={:?}", bg));
+                }
+                if !modifier.is_empty() {
+                    parts.push(format!("{:?}", modifier));
+                }
+                if !parts.is_empty() {
+                    styles.push_str(&format!(
+                        "{} {}-{} {}\n",
+                        y,
+                        start,
+                        end - 1,
+                        parts.join(" ")
+                    ));
+                }
+                start = end;
+            }
+        }
+        styles
+    }
+
+    /// The text of the buffer followed by its styles.
+    fn snapshot(buffer: &Buffer) -> String {
+        format!("{}--- styles\n{}", text(buffer), styles(buffer))
+    }
+
+    /// Compares the rendering of `app` at every size with the stored
+    /// snapshots in `src/ui/snapshots`. Run the tests with
+    /// `UPDATE_SNAPSHOTS=1` to write them after an intended change.
+    fn assert_snapshots(name: &str, app: &App) {
+        let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+        let mut failures = Vec::new();
+        for (width, height) in SIZES {
+            let actual = snapshot(&render(app, width, height));
+            let path = snapshot_path(&format!("{}_{}x{}", name, width, height));
+            if update {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, &actual).unwrap();
+                continue;
+            }
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => failures.push(format!(
+                    "{} differs from the snapshot\n--- expected\n{}--- actual\n{}",
+                    path.display(),
+                    expected,
+                    actual
+                )),
+                Err(_) => failures.push(format!(
+                    "{} is missing, run the tests with UPDATE_SNAPSHOTS=1 to write it\n{}",
+                    path.display(),
+                    actual
+                )),
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    fn snapshot_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src/ui/snapshots")
+            .join(format!("{}.txt", name))
+    }
+
+    #[test]
+    fn test_normal_mode() {
+        assert_snapshots("normal", &app());
+    }
+
+    #[test]
+    fn test_normal_mode_with_a_draft() {
+        assert_snapshots("normal_draft", &filled_app());
+    }
+
+    #[test]
+    fn test_dark_theme() {
+        let mut app = filled_app();
+        app.theme = Theme::named(ThemeName::Dark);
+        app.enter_edit_mode(Field::Title.index());
+        assert_snapshots("dark", &app);
+    }
+
+    #[test]
+    fn test_editing_mode() {
+        let mut app = filled_app();
+        app.enter_edit_mode(Field::Description.index());
+        assert_eq!(app.input_mode, InputMode::Editing);
+        assert_snapshots("editing", &app);
+    }
+
+    #[test]
+    fn test_confirm_popup() {
+        let mut app = filled_app();
+        app.enter_edit_mode(0);
+        app.confirm_pull_request();
+        assert_eq!(app.input_mode, InputMode::Creating);
+        assert_snapshots("confirm", &app);
+    }
+
+    #[test]
+    fn test_pat_popup() {
+        let mut app = app();
+        app.show_pat_popup = true;
+        assert_snapshots("pat", &app);
+    }
+
+    #[test]
+    fn test_exit_popup() {
+        let mut app = app();
+        app.show_exit_popup = true;
+        assert_snapshots("exit", &app);
+    }
+
+    #[test]
+    fn test_help_overlay() {
+        let mut app = filled_app();
+        app.token_user = Some(Ok("djego".to_string()));
+        app.show_help = true;
+        assert_snapshots("help", &app);
+        app.enter_edit_mode(0);
+        assert_snapshots("help_editing", &app);
+    }
+
+    #[test]
+    fn test_error_message() {
+        let mut app = filled_app();
+        app.messages.push(message(
+            Severity::Error,
+            "Failed to create pull request: Validation Failed",
+            30,
+        ));
+        assert_snapshots("error", &app);
+    }
+
+    #[test]
+    fn test_success_message() {
+        let mut app = app();
+        app.messages.push(message(
+            Severity::Success,
+            "Repository has been synced successfully ✅",
+            30,
+        ));
+        assert_snapshots("success", &app);
+    }
+
+    #[test]
+    fn test_message_log() {
+        let mut app = app();
+        app.messages.push(message(
+            Severity::Warning,
+            "Keymap: Unknown key \"Hyper+x\"",
+            28,
+        ));
+        app.messages.push(message(
+            Severity::Error,
+            "Failed to sync: Bad credentials\n Hint: check the token in the config",
+            29,
+        ));
+        app.messages.push(message(
+            Severity::Success,
+            "Repository has been synced successfully ✅",
+            30,
+        ));
+        app.messages.dismiss();
+        assert_snapshots("output", &app);
+        app.screen = Screen::Messages;
+        assert_snapshots("messages", &app);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::core::crash::write_crash_report;
+use crossterm::{
+    cursor::Show,
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::io::{self, Stdout};
+use std::panic;
+
+/// Owns the terminal while the TUI is running. Raw mode and the alternate
+/// screen are restored when the guard is dropped, whichever way `main` exits.
+pub struct TerminalGuard {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    mouse: bool,
+}
+
+impl TerminalGuard {
+    /// Takes over the terminal, capturing the mouse when `mouse` is set.
+    pub fn new(mouse: bool) -> Result<TerminalGuard, io::Error> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        if let Err(e) = enter(&mut stdout, mouse) {
+            let _ = restore();
+            return Err(e);
+        }
+        let terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+        Ok(TerminalGuard { terminal, mouse })
+    }
+
+    pub fn terminal(&mut self) -> &mut Terminal<CrosstermBackend<Stdout>> {
+        &mut self.terminal
+    }
+
+    /// Hands the terminal over to `run`, for programs such as an editor that
+    /// need the main screen, and takes it back afterwards.
+    pub fn suspend<T>(&mut self, run: impl FnOnce() -> T) -> Result<T, io::Error> {
+        restore()?;
+        let result = run();
+        enable_raw_mode()?;
+        enter(&mut io::stdout(), self.mouse)?;
+        self.terminal.clear()?;
+        Ok(result)
+    }
+}
+
+fn enter(stdout: &mut Stdout, mouse: bool) -> Result<(), io::Error> {
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = restore();
+    }
+}
+
+pub fn restore() -> Result<(), io::Error> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        LeaveAlternateScreen,
+        Show
+    )?;
+    Ok(())
+}
+
+/// Restores the terminal before the default hook prints the panic, so the
+/// message is readable, and records a crash report next to the config.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore();
+        default_hook(info);
+        match write_crash_report(info) {
+            Ok(path) => eprintln!("A crash report was written to {}", path.display()),
+            Err(e) => eprintln!("Failed to write crash report: {}", e),
+        }
+    }));
+}
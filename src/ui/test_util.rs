@@ -0,0 +1,45 @@
+use crate::core::app::App;
+use crate::ui::layout::ui;
+use crate::ui::mouse::Regions;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+
+/// An app that renders the same wherever the tests run: no config, git
+/// checkout or API responses leak into it.
+pub fn app() -> App {
+    let mut app = App::with_config(
+        "djego".to_string(),
+        "prt".to_string(),
+        "feature/snapshots",
+        None,
+        false,
+    );
+    app.github_repository
+        .set_url("https://github.com/djego/prt".to_string());
+    app.github_repository.set_default_branch("main".to_string());
+    app
+}
+
+/// Draws `app` on a `width` x `height` terminal, returning what was drawn
+/// and where it can be clicked.
+pub fn draw(app: &App, width: u16, height: u16) -> (Buffer, Regions) {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    let mut regions = Regions::default();
+    terminal.draw(|f| regions = ui(f, app)).unwrap();
+    (terminal.backend().buffer().clone(), regions)
+}
+
+pub fn render(app: &App, width: u16, height: u16) -> Buffer {
+    draw(app, width, height).0
+}
+
+/// The symbols of each row of `buffer`.
+pub fn lines(buffer: &Buffer) -> Vec<String> {
+    let width = buffer.area.width as usize;
+    buffer
+        .content
+        .chunks(width)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect()
+}